@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+// most errors name the optional account a configured feature needs
+#[allow(clippy::enum_variant_names)]
+#[error_code]
+pub enum GrowthError {
+    #[msg("Org requires promotion approval, pass the `promotion` account")]
+    PromotionAccountMissing,
+    #[msg("Org charges an SPL fee, pass the `fee_token_account` and `treasury_token_account` accounts")]
+    FeeTokenAccountMissing,
+    #[msg("Org pays level rewards, pass the `reward_claims` account")]
+    RewardClaimsMissing,
+    #[msg("The review was rewarded, pass the reviewer's `reviewer_rewards` account")]
+    ReviewerRewardsMissing,
+    #[msg("The review counts as sent by a member, pass the reviewer's `reviewer_score` account")]
+    ReviewerScoreMissing,
+    #[msg("The review was revealed, pass its settled `reveal_batch` account")]
+    RevealBatchMissing,
+    #[msg("Member is on a team, pass the `team` account")]
+    TeamAccountMissing,
+    #[msg("Score account is from an older layout, run `migrate_score` first")]
+    ScoreNotMigrated,
+    #[msg("Withdrawing SPL tokens, pass the `destination_token_account` account")]
+    DestinationTokenAccountMissing,
+    #[msg("Member levels don't match the org's levels")]
+    LevelOutOfRange,
+    #[msg("Score NFT isn't verified in the org's collection")]
+    CollectionNotVerified,
+    #[msg("Member is still pending approval")]
+    MemberPending,
+    #[msg("Range group out of bounds")]
+    RangeGroupOutOfBounds,
+    #[msg("Member's level is below the required level")]
+    LevelTooLow,
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Relationship, ScoreSnapshot};
+
+#[event]
+pub struct ScoresOverridden {
+    pub org: Pubkey,
+    pub member: Pubkey,
+    pub sequence: u32,
+    pub signer: Pubkey,
+    pub reason_hash: [u8; 32],
+    pub timestamp: i64,
+    pub before: ScoreSnapshot,
+    pub after: ScoreSnapshot,
+}
+
+#[event]
+pub struct ScoreReceived {
+    pub org: Pubkey,
+    pub member: Pubkey,
+    pub signer: Pubkey,
+    pub scores: Vec<f32>,
+    pub relationship: Relationship,
+    pub submission_ts: i64,
+    pub backdated: bool,
+}
+
+#[event]
+pub struct RegistrationFeePaid {
+    pub org: Pubkey,
+    pub member: Pubkey,
+    pub payer: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub org: Pubkey,
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct LevelRewardPaid {
+    pub org: Pubkey,
+    pub member: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub levels: Vec<u8>,
+}
+
+#[event]
+pub struct ReviewRewardPaid {
+    pub org: Pubkey,
+    pub reviewer: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ReviewRewardClawedBack {
+    pub org: Pubkey,
+    pub reviewer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ReviewRevoked {
+    pub org: Pubkey,
+    pub member: Pubkey,
+    pub reviewer: Pubkey,
+    pub index: u32,
+    pub revoked_by: Pubkey,
+}
+
+#[event]
+pub struct DisputeOpened {
+    pub org: Pubkey,
+    pub member: Pubkey,
+    pub review: Pubkey,
+    pub reason_hash: [u8; 32],
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub org: Pubkey,
+    pub member: Pubkey,
+    pub review: Pubkey,
+    pub upheld: bool,
+    pub resolution_hash: [u8; 32],
+}
+
+#[event]
+pub struct ManagerReassigned {
+    pub org: Pubkey,
+    pub member: Pubkey,
+    pub old_manager: Pubkey,
+    pub new_manager: Pubkey,
+}
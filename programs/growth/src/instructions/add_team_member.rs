@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Org, Score, Team};
+
+#[derive(Accounts)]
+pub struct TeamMemberCTX<'info> {
+    pub authority: Signer<'info>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account()]
+    pub applicant: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
+        bump,
+    )]
+    pub score: Account<'info, Score>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(
+        mut,
+        seeds = [b"team", org.key().as_ref(), team.name.as_bytes()],
+        bump = team.bump,
+    )]
+    pub team: Account<'info, Team>,
+}
+
+// A member is on at most one team, move them with remove_team_member first.
+pub fn add_team_member(ctx: Context<TeamMemberCTX>) -> Result<()> {
+    assert_eq!(
+        ctx.accounts.org.authority.key(),
+        ctx.accounts.authority.key()
+    );
+    let score = &mut ctx.accounts.score;
+    assert!(!score.pending);
+    assert_eq!(score.team, Pubkey::default());
+    score.team = ctx.accounts.team.key();
+    ctx.accounts
+        .team
+        .aggregate
+        .add_member(&ctx.accounts.org, &score.scores, &score.levels)
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::state::{Org, Score, ScoreHistory, Treasury, PAUSE_REGISTRATIONS};
+use crate::utils::{collect_registration_fee, FeeAccounts, Realloc};
+
+#[derive(Accounts)]
+pub struct ApplyCTX<'info> {
+    #[account(mut)]
+    pub applicant: Signer<'info>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(
+        init,
+        payer = applicant,
+        seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
+        bump,
+        space = 8 + std::mem::size_of::<Score>()
+    )]
+    pub score: Account<'info, Score>,
+    #[account(
+        init,
+        payer = applicant,
+        seeds = [b"history", score.key().as_ref()],
+        bump,
+        space = ScoreHistory::space(&org)
+    )]
+    pub history: Account<'info, ScoreHistory>,
+    #[account(
+        init_if_needed,
+        payer = applicant,
+        seeds = [b"treasury", org.key().as_ref()],
+        bump,
+        space = Treasury::SPACE
+    )]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut, token::mint = org.fee_mint)]
+    pub fee_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(mut, token::mint = org.fee_mint, token::authority = treasury)]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn apply(ctx: Context<ApplyCTX>, name: String) -> Result<()> {
+    assert!(!ctx.accounts.org.is_paused(PAUSE_REGISTRATIONS));
+    // self-service registrations are always paid by the applicant, the only signer
+    collect_registration_fee(
+        &ctx.accounts.org,
+        ctx.accounts.applicant.key(),
+        FeeAccounts {
+            payer: ctx.accounts.applicant.to_account_info(),
+            treasury: &mut ctx.accounts.treasury,
+            treasury_bump: *ctx.bumps.get("treasury").unwrap(),
+            fee_token_account: ctx.accounts.fee_token_account.as_ref(),
+            treasury_token_account: ctx.accounts.treasury_token_account.as_ref(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+    )?;
+    let org = &ctx.accounts.org;
+    ctx.accounts
+        .score
+        .set_inner(Score::new(*ctx.bumps.get("score").unwrap()));
+    let score_key = ctx.accounts.score.key();
+    ctx.accounts
+        .history
+        .ensure_initialized(score_key, *ctx.bumps.get("history").unwrap());
+    let space_to_add = Score::space_to_add(org, &name);
+    ctx.accounts.score.realloc(
+        space_to_add,
+        &ctx.accounts.applicant,
+        &ctx.accounts.system_program,
+    )?;
+    ctx.accounts.score.init_member(
+        org,
+        name,
+        ctx.accounts.applicant.key(),
+        vec![0; org.levels.len()],
+        0,
+    );
+    ctx.accounts.score.pending = true;
+    msg!("Application submitted");
+    Ok(())
+}
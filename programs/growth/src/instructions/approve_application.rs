@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::state::{Org, Score, PAUSE_REGISTRATIONS};
+use crate::utils::{create_score_nft, ScoreNftAccounts};
+
+#[derive(Accounts)]
+pub struct ApproveApplicationCTX<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: The applicant consented by signing `apply`
+    #[account()]
+    pub applicant: AccountInfo<'info>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(
+        mut,
+        seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
+        bump,
+    )]
+    pub score: Account<'info, Score>,
+    #[account(
+        mut,
+        mint::decimals = 0,
+        mint::authority = org,
+        mint::freeze_authority = org,
+        constraint = register_mint.supply == 0,
+    )]
+    pub register_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        constraint = token_account.mint == register_mint.key(),
+        constraint = token_account.owner == applicant.key(),
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    /// CHECK: The org PDA signs the metadata creation, so only the real program is accepted
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub rent: AccountInfo<'info>,
+}
+
+pub fn approve_application(
+    ctx: Context<ApproveApplicationCTX>,
+    levels: Vec<u8>,
+    last_update: i64,
+) -> Result<()> {
+    assert!(!ctx.accounts.org.is_paused(PAUSE_REGISTRATIONS));
+    assert_eq!(
+        ctx.accounts.org.authority.key(),
+        ctx.accounts.authority.key()
+    );
+    assert!(ctx.accounts.score.pending);
+    assert_eq!(levels.len(), ctx.accounts.org.levels.len());
+
+    let score = &mut ctx.accounts.score;
+    score.levels = levels;
+    score.last_update = last_update;
+    score.mint = ctx.accounts.register_mint.key();
+    score.pending = false;
+    msg!("Application approved");
+
+    create_score_nft(
+        &ctx.accounts.org,
+        ScoreNftAccounts {
+            payer: ctx.accounts.authority.to_account_info(),
+            register_mint: ctx.accounts.register_mint.to_account_info(),
+            token_account: ctx.accounts.token_account.to_account_info(),
+            metadata: ctx.accounts.metadata.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        },
+        &score.name,
+        &score.levels,
+    )
+}
@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::MetadataAccount,
+    token::{Token, TokenAccount},
+};
+
+use crate::{
+    state::{
+        LevelChange, LevelChangeSource, Org, PendingPromotion, PromotionStatus, RewardClaims,
+        Score, ScoreHistory, Team, PAUSE_LEVEL_CHANGES,
+    },
+    utils::{pay_level_rewards, sync_team, update_score_uri, RewardAccounts},
+};
+
+#[derive(Accounts)]
+pub struct ApprovePromotionCTX<'info> {
+    #[account(mut)]
+    pub approver: Signer<'info>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account()]
+    pub applicant: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
+        bump,
+    )]
+    pub score: Account<'info, Score>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(address = org.parent)]
+    pub parent: Option<Account<'info, Org>>,
+    #[account(
+        mut,
+        seeds = [b"history", score.key().as_ref()],
+        bump = history.bump,
+    )]
+    pub history: Account<'info, ScoreHistory>,
+    #[account(
+        mut,
+        seeds = [b"promotion", score.key().as_ref()],
+        bump = promotion.bump,
+    )]
+    pub promotion: Account<'info, PendingPromotion>,
+    #[account(
+        init_if_needed,
+        payer = approver,
+        seeds = [b"reward_claims", score.key().as_ref()],
+        bump,
+        space = RewardClaims::space(&org)
+    )]
+    pub reward_claims: Option<Account<'info, RewardClaims>>,
+    #[account(mut, token::mint = org.reward_mint, token::authority = org)]
+    pub reward_vault: Option<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = org.reward_mint,
+        associated_token::authority = applicant
+    )]
+    pub member_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub team: Option<Account<'info, Team>>,
+    #[account(mut, constraint = metadata.mint == score.mint)]
+    pub metadata: Account<'info, MetadataAccount>,
+    /// CHECK: The org PDA signs the metadata update, so only the real program is accepted
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn approve_promotion(ctx: Context<ApprovePromotionCTX>) -> Result<()> {
+    assert!(!ctx.accounts.org.is_paused(PAUSE_LEVEL_CHANGES));
+    let score = &mut ctx.accounts.score;
+    let promotion = &mut ctx.accounts.promotion;
+    let clock = Clock::get()?;
+    assert!(
+        ctx.accounts
+            .org
+            .can_approve_promotions(ctx.accounts.approver.key, ctx.accounts.parent.as_ref())
+            || score.is_manager(ctx.accounts.approver.key)
+    );
+    assert_eq!(promotion.status, PromotionStatus::Pending);
+    // scores may have been overwritten since the request was made
+    assert_eq!(promotion.old_levels, score.levels);
+    assert!(score.can_change_to(
+        &ctx.accounts.org,
+        &promotion.new_levels,
+        clock.unix_timestamp
+    ));
+
+    ctx.accounts.history.record(LevelChange {
+        timestamp: clock.unix_timestamp,
+        old_levels: score.levels.clone(),
+        new_levels: promotion.new_levels.clone(),
+        source: LevelChangeSource::ApprovePromotion,
+        signer: ctx.accounts.approver.key(),
+    });
+    let old_levels = std::mem::replace(&mut score.levels, promotion.new_levels.clone());
+    score.last_update = clock.unix_timestamp;
+    sync_team(
+        &ctx.accounts.org,
+        score,
+        ctx.accounts.team.as_mut(),
+        &score.scores,
+        &old_levels,
+    )?;
+    promotion.status = PromotionStatus::Approved;
+    promotion.reviewed_by = ctx.accounts.approver.key();
+    promotion.reviewed_at = clock.unix_timestamp;
+
+    update_score_uri(
+        &ctx.accounts.org,
+        &ctx.accounts.metadata,
+        &ctx.accounts.token_metadata_program,
+        &score.levels,
+    )?;
+    let token_program = ctx.accounts.token_program.to_account_info();
+    pay_level_rewards(
+        &ctx.accounts.org,
+        score,
+        RewardAccounts::from_optional(
+            ctx.accounts.reward_claims.as_mut(),
+            ctx.bumps.get("reward_claims").copied(),
+            ctx.accounts.reward_vault.as_ref(),
+            ctx.accounts.member_token_account.as_ref(),
+            token_program,
+        ),
+        &old_levels,
+    )
+}
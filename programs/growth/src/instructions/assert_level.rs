@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::MetadataAccount;
+
+use crate::errors::GrowthError;
+use crate::state::{Org, Score};
+
+// Read-only accounts for other programs checking a member's levels over CPI.
+#[derive(Accounts)]
+pub struct LevelCheckCTX<'info> {
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account()]
+    pub applicant: AccountInfo<'info>,
+    #[account(
+        seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
+        bump,
+    )]
+    pub score: Account<'info, Score>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(constraint = metadata.mint == score.mint)]
+    pub metadata: Account<'info, MetadataAccount>,
+}
+
+// Only approved members whose score NFT is verified in the org's collection have
+// levels as far as other programs are concerned.
+pub fn verified_levels(ctx: &Context<LevelCheckCTX>) -> Result<Vec<u8>> {
+    require!(
+        matches!(
+            &ctx.accounts.metadata.collection,
+            Some(collection) if collection.verified && collection.key == ctx.accounts.org.mint
+        ),
+        GrowthError::CollectionNotVerified
+    );
+    require!(!ctx.accounts.score.pending, GrowthError::MemberPending);
+    Ok(ctx.accounts.score.levels.clone())
+}
+
+pub fn assert_level(ctx: Context<LevelCheckCTX>, range_group: u8, min_level: u8) -> Result<()> {
+    let levels = verified_levels(&ctx)?;
+    let level = *levels
+        .get(range_group as usize)
+        .ok_or(GrowthError::RangeGroupOutOfBounds)?;
+    msg!(
+        "range group:{} level:{} required:{}",
+        range_group,
+        level,
+        min_level
+    );
+    require!(level >= min_level, GrowthError::LevelTooLow);
+    Ok(())
+}
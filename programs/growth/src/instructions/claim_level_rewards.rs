@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::{
+    state::{Org, RewardClaims, Score},
+    utils::pay_owed_rewards,
+};
+
+#[derive(Accounts)]
+pub struct ClaimLevelRewardsCTX<'info> {
+    pub cranker: Signer<'info>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account()]
+    pub applicant: AccountInfo<'info>,
+    #[account(
+        seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
+        bump,
+    )]
+    pub score: Account<'info, Score>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(
+        mut,
+        seeds = [b"reward_claims", score.key().as_ref()],
+        bump = reward_claims.bump,
+    )]
+    pub reward_claims: Account<'info, RewardClaims>,
+    #[account(mut, token::mint = org.reward_mint, token::authority = org)]
+    pub reward_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = org.reward_mint,
+        associated_token::authority = applicant
+    )]
+    pub member_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn claim_level_rewards(ctx: Context<ClaimLevelRewardsCTX>) -> Result<()> {
+    let owed = ctx.accounts.reward_claims.owed;
+    assert!(owed > 0);
+    // a partial payout would leave the rest unaccounted for, so wait for the vault
+    assert!(ctx.accounts.reward_vault.amount >= owed);
+    pay_owed_rewards(
+        &ctx.accounts.org,
+        &ctx.accounts.score,
+        &mut ctx.accounts.reward_claims,
+        Some(&ctx.accounts.reward_vault),
+        Some(&ctx.accounts.member_token_account),
+        ctx.accounts.token_program.to_account_info(),
+    )
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::{
+    state::{Org, ReviewRecord, ReviewerRewards, Score, PAUSE_REVIEWS},
+    utils::{pay_review_reward, ReviewRewardAccounts},
+};
+
+#[derive(Accounts)]
+#[instruction(index: u32)]
+pub struct ClaimReviewRewardCTX<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: The reviewer, checked against the review record
+    #[account()]
+    pub applicant: AccountInfo<'info>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account()]
+    pub reviewee: AccountInfo<'info>,
+    #[account(
+        seeds = [b"score", org.key().as_ref(), reviewee.key().as_ref()],
+        bump,
+    )]
+    pub reviewee_score: Account<'info, Score>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(
+        mut,
+        seeds = [b"review", reviewee_score.key().as_ref(), index.to_le_bytes().as_ref()],
+        bump = review_record.bump,
+        constraint = review_record.reviewer == applicant.key(),
+    )]
+    pub review_record: Account<'info, ReviewRecord>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"reviewer_rewards", org.key().as_ref(), applicant.key().as_ref()],
+        bump,
+        space = ReviewerRewards::SPACE
+    )]
+    pub reviewer_rewards: Account<'info, ReviewerRewards>,
+    #[account(mut, token::mint = org.reward_mint, token::authority = org)]
+    pub reward_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = org.reward_mint,
+        associated_token::authority = applicant
+    )]
+    pub member_token_account: Account<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+// Rewards the reviewer (`applicant`) for one recorded review. The amount is kept on
+// the review record so revoking the review claws it back.
+pub fn claim_review_reward(ctx: Context<ClaimReviewRewardCTX>, _index: u32) -> Result<()> {
+    assert!(!ctx.accounts.org.is_paused(PAUSE_REVIEWS));
+    let org = &ctx.accounts.org;
+    assert_eq!(org.authority.key(), ctx.accounts.authority.key());
+    let review_record = &mut ctx.accounts.review_record;
+    assert!(!review_record.revoked);
+    assert!(!review_record.rewarded);
+    let amount = pay_review_reward(
+        org,
+        ctx.accounts.applicant.key(),
+        ReviewRewardAccounts {
+            reviewer_rewards: &mut ctx.accounts.reviewer_rewards,
+            reviewer_rewards_bump: *ctx.bumps.get("reviewer_rewards").unwrap(),
+            vault: &ctx.accounts.reward_vault,
+            reviewer_token_account: &ctx.accounts.member_token_account,
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+    )?;
+    review_record.rewarded = true;
+    review_record.reward_paid = amount;
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Org, ReviewCommitment, Score, PAUSE_REVIEWS};
+
+#[derive(Accounts)]
+#[instruction(commitment: [u8; 32])]
+pub struct CommitReviewCTX<'info> {
+    #[account(mut)]
+    pub reviewer: Signer<'info>,
+    #[account(
+        seeds = [b"score", org.key().as_ref(), reviewer.key().as_ref()],
+        bump,
+        constraint = !reviewer_score.pending,
+    )]
+    pub reviewer_score: Account<'info, Score>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(
+        init,
+        payer = reviewer,
+        seeds = [
+            b"commitment",
+            org.key().as_ref(),
+            reviewer.key().as_ref(),
+            commitment.as_ref()
+        ],
+        bump,
+        space = ReviewCommitment::SPACE
+    )]
+    pub review_commitment: Account<'info, ReviewCommitment>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn commit_review(ctx: Context<CommitReviewCTX>, commitment: [u8; 32]) -> Result<()> {
+    let org = &ctx.accounts.org;
+    assert!(org.commit_reveal);
+    assert!(!org.is_paused(PAUSE_REVIEWS));
+    let now = Clock::get()?.unix_timestamp;
+    let cycle = org.review_cycle(now);
+    assert!(now < org.reveal_start(cycle));
+    ctx.accounts.review_commitment.set_inner(ReviewCommitment {
+        org: org.key(),
+        reviewer: ctx.accounts.reviewer.key(),
+        cycle,
+        commitment,
+        revealed: false,
+        bump: *ctx.bumps.get("review_commitment").unwrap(),
+    });
+    Ok(())
+}
@@ -11,7 +11,10 @@ use anchor_spl::{
 };
 use mpl_token_metadata::state::CollectionDetails;
 
-use crate::{state::Org, utils::Realloc};
+use crate::{
+    state::{FeePayer, Org, ScoringMethod, RELATIONSHIP_TYPES},
+    utils::Realloc,
+};
 
 #[derive(Accounts)]
 pub struct CreateOrgCTX<'info> {
@@ -72,6 +75,37 @@ pub fn create_organization(
     org.min_reviews = min_reviews;
     org.domain = domain;
     org.level_wait = level_wait;
+    org.scoring_method = ScoringMethod::default();
+    org.confidence_z = 0.0;
+    org.require_promotion_approval = false;
+    org.promotion_approver = ctx.accounts.authority.key();
+    org.max_backdate = 0;
+    org.import_enabled = false;
+    org.allowlist_root = [0; 32];
+    org.paused = 0;
+    org.review_period = 0;
+    org.reviewer_cycle = 0;
+    org.max_reviews_per_cycle = 0;
+    org.max_pair_reviews_per_period = 0;
+    org.min_review_interval = 0;
+    org.registration_fee = 0;
+    org.fee_mint = Pubkey::default();
+    org.fee_payer = FeePayer::default();
+    org.reward_mint = Pubkey::default();
+    org.level_rewards = vec![];
+    org.review_reward = 0;
+    org.max_review_rewards_per_period = 0;
+    org.min_reviews_sent = 0;
+    org.reviews_sent_per_period = false;
+    org.review_grace_period = 0;
+    org.commit_reveal = false;
+    org.reveal_window = 0;
+    org.reputation_weights = vec![];
+    org.relationship_weights = [1; RELATIONSHIP_TYPES];
+    org.include_self_reviews = false;
+    org.parent = Pubkey::default();
+    org.parent_range_map = vec![];
+    org.parent_epoch = 0;
     let mut total_levels = 0;
     for l in org.levels.iter() {
         for _l2 in l.iter() {
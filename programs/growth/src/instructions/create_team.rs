@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Org, Team, MAX_TEAM_NAME_LEN};
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct CreateTeamCTX<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"team", org.key().as_ref(), name.as_bytes()],
+        bump,
+        space = Team::space(&org, &name)
+    )]
+    pub team: Account<'info, Team>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_team(ctx: Context<CreateTeamCTX>, name: String, lead: Pubkey) -> Result<()> {
+    assert_eq!(
+        ctx.accounts.org.authority.key(),
+        ctx.accounts.authority.key()
+    );
+    assert!(!name.is_empty() && name.len() <= MAX_TEAM_NAME_LEN);
+    msg!("team:{} lead:{}", name, lead);
+    ctx.accounts.team.init(
+        &ctx.accounts.org,
+        name,
+        lead,
+        *ctx.bumps.get("team").unwrap(),
+    );
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::MetadataAccount,
+    token::{Token, TokenAccount},
+};
+
+use crate::{
+    errors::GrowthError,
+    state::{
+        LevelChange, LevelChangeSource, Org, PendingPromotion, RewardClaims, Score, ScoreHistory,
+        Team, PAUSE_LEVEL_CHANGES,
+    },
+    utils::{pay_level_rewards, sync_team, update_score_uri, RewardAccounts},
+};
+
+#[derive(Accounts)]
+pub struct EvaluateLevelCTX<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account()]
+    pub applicant: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
+        bump,
+        constraint = score.is_migrated(&org) @ GrowthError::ScoreNotMigrated,
+    )]
+    pub score: Account<'info, Score>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        seeds = [b"history", score.key().as_ref()],
+        bump,
+        space = ScoreHistory::space(&org)
+    )]
+    pub history: Account<'info, ScoreHistory>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        seeds = [b"promotion", score.key().as_ref()],
+        bump,
+        space = PendingPromotion::space(&org)
+    )]
+    pub promotion: Option<Account<'info, PendingPromotion>>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        seeds = [b"reward_claims", score.key().as_ref()],
+        bump,
+        space = RewardClaims::space(&org)
+    )]
+    pub reward_claims: Option<Account<'info, RewardClaims>>,
+    #[account(mut, token::mint = org.reward_mint, token::authority = org)]
+    pub reward_vault: Option<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = org.reward_mint,
+        associated_token::authority = applicant
+    )]
+    pub member_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub team: Option<Account<'info, Team>>,
+    #[account(mut, constraint = metadata.mint == score.mint)]
+    pub metadata: Account<'info, MetadataAccount>,
+    /// CHECK: The org PDA signs the metadata update, so only the real program is accepted
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn evaluate_level(ctx: Context<EvaluateLevelCTX>) -> Result<()> {
+    assert!(!ctx.accounts.org.is_paused(PAUSE_LEVEL_CHANGES));
+    let score = &mut ctx.accounts.score;
+    assert!(!score.pending);
+    let clock = Clock::get()?;
+    // members from before level history don't have the account yet
+    ctx.accounts
+        .history
+        .ensure_initialized(score.key(), *ctx.bumps.get("history").unwrap());
+
+    let old_scores = score.scores.clone();
+    let next_level = score.reconcile(&ctx.accounts.org);
+    sync_team(
+        &ctx.accounts.org,
+        score,
+        ctx.accounts.team.as_mut(),
+        &old_scores,
+        &score.levels,
+    )?;
+
+    if !ctx.accounts.metadata.collection.as_ref().unwrap().verified {
+        return Ok(());
+    }
+
+    msg!(
+        "evaluate last_update:{} level_wait:{} current_ts:{} current_level:{:?} next_level:{:?}",
+        score.last_update,
+        ctx.accounts.org.level_wait,
+        clock.unix_timestamp,
+        score.levels,
+        next_level
+    );
+    if score.levels == next_level
+        || !score.level_change_due(&ctx.accounts.org, clock.unix_timestamp)
+        || !score.can_change_to(&ctx.accounts.org, &next_level, clock.unix_timestamp)
+    {
+        return Ok(());
+    }
+    if ctx.accounts.org.require_promotion_approval {
+        ctx.accounts
+            .promotion
+            .as_mut()
+            .ok_or(GrowthError::PromotionAccountMissing)?
+            .request(
+                score.key(),
+                score.levels.clone(),
+                next_level,
+                clock.unix_timestamp,
+                ctx.accounts.cranker.key(),
+                *ctx.bumps.get("promotion").unwrap(),
+            );
+        return Ok(());
+    }
+    ctx.accounts.history.record(LevelChange {
+        timestamp: clock.unix_timestamp,
+        old_levels: score.levels.clone(),
+        new_levels: next_level.clone(),
+        source: LevelChangeSource::EvaluateLevel,
+        signer: ctx.accounts.cranker.key(),
+    });
+    let old_levels = std::mem::replace(&mut score.levels, next_level);
+    score.last_update = clock.unix_timestamp;
+    sync_team(
+        &ctx.accounts.org,
+        score,
+        ctx.accounts.team.as_mut(),
+        &score.scores,
+        &old_levels,
+    )?;
+    update_score_uri(
+        &ctx.accounts.org,
+        &ctx.accounts.metadata,
+        &ctx.accounts.token_metadata_program,
+        &score.levels,
+    )?;
+    let token_program = ctx.accounts.token_program.to_account_info();
+    pay_level_rewards(
+        &ctx.accounts.org,
+        score,
+        RewardAccounts::from_optional(
+            ctx.accounts.reward_claims.as_mut(),
+            ctx.bumps.get("reward_claims").copied(),
+            ctx.accounts.reward_vault.as_ref(),
+            ctx.accounts.member_token_account.as_ref(),
+            token_program,
+        ),
+        &old_levels,
+    )
+}
@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+use super::{verified_levels, LevelCheckCTX};
+
+// The levels are passed back through `set_return_data`.
+pub fn get_levels(ctx: Context<LevelCheckCTX>) -> Result<Vec<u8>> {
+    verified_levels(&ctx)
+}
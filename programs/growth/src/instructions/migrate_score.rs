@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{Org, Score},
+    utils::Realloc,
+};
+
+#[derive(Accounts)]
+pub struct MigrateScoreCTX<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account()]
+    pub applicant: AccountInfo<'info>,
+    /// CHECK: An old account can be too short to load as `Score`, so it's checked by
+    /// seeds and owner and read by hand
+    #[account(
+        mut,
+        seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub score: UncheckedAccount<'info>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    pub system_program: Program<'info, System>,
+}
+
+// Permissionless, the payer covers the rent if the account has to grow.
+pub fn migrate_score(ctx: Context<MigrateScoreCTX>) -> Result<()> {
+    let org = &ctx.accounts.org;
+    let mut data = ctx.accounts.score.try_borrow_data()?.to_vec();
+    // fields appended after the account was created read as zeros
+    data.resize(data.len() + 8 + std::mem::size_of::<Score>(), 0);
+    let mut score = Score::try_deserialize(&mut data.as_slice())?;
+    if score.is_migrated(org) {
+        msg!("Score already migrated");
+        return Ok(());
+    }
+    score.migrate(org);
+
+    let mut migrated = vec![];
+    score.try_serialize(&mut migrated)?;
+    let data_len = ctx.accounts.score.data_len();
+    if migrated.len() > data_len {
+        ctx.accounts.score.realloc(
+            migrated.len() - data_len,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+        )?;
+    }
+    ctx.accounts.score.try_borrow_mut_data()?[..migrated.len()].copy_from_slice(&migrated);
+    Ok(())
+}
@@ -1,13 +1,99 @@
+mod add_team_member;
+mod apply;
+mod approve_application;
+mod approve_promotion;
+mod assert_level;
+mod claim_level_rewards;
+mod claim_review_reward;
+mod commit_review;
 mod create_organization;
+mod create_team;
+mod evaluate_level;
+mod get_levels;
+mod migrate_score;
+mod open_dispute;
+mod preview_level;
+mod reassign_manager;
 mod receive_score;
+mod receive_signed_score;
 mod register;
+mod register_with_proof;
+mod reject_promotion;
+mod remove_team_member;
+mod resolve_dispute;
+mod reveal_review;
+mod revoke_review;
+mod roll_up_score;
 mod send_score;
+mod set_allowlist_root;
+mod set_commit_reveal;
+mod set_confidence;
+mod set_level_rewards;
+mod set_max_backdate;
+mod set_min_reviews_sent;
+mod set_parent_org;
+mod set_paused;
+mod set_promotion_approval;
+mod set_registration_fee;
+mod set_relationship_weights;
+mod set_reputation_weights;
+mod set_review_grace_period;
+mod set_review_limits;
+mod set_review_reward;
+mod set_scoring_method;
+mod set_team_lead;
+mod settle_reveals;
+mod sync_metadata;
 mod verify;
 mod update_scores;
+mod withdraw_treasury;
 
+pub use add_team_member::*;
+pub use apply::*;
+pub use approve_application::*;
+pub use approve_promotion::*;
+pub use assert_level::*;
+pub use claim_level_rewards::*;
+pub use claim_review_reward::*;
+pub use commit_review::*;
 pub use create_organization::*;
+pub use create_team::*;
+pub use evaluate_level::*;
+pub use get_levels::*;
+pub use migrate_score::*;
+pub use open_dispute::*;
+pub use preview_level::*;
+pub use reassign_manager::*;
 pub use receive_score::*;
+pub use receive_signed_score::*;
 pub use register::*;
+pub use register_with_proof::*;
+pub use reject_promotion::*;
+pub use remove_team_member::*;
+pub use resolve_dispute::*;
+pub use reveal_review::*;
+pub use revoke_review::*;
+pub use roll_up_score::*;
 pub use send_score::*;
+pub use set_allowlist_root::*;
+pub use set_commit_reveal::*;
+pub use set_confidence::*;
+pub use set_level_rewards::*;
+pub use set_max_backdate::*;
+pub use set_min_reviews_sent::*;
+pub use set_parent_org::*;
+pub use set_paused::*;
+pub use set_promotion_approval::*;
+pub use set_registration_fee::*;
+pub use set_relationship_weights::*;
+pub use set_reputation_weights::*;
+pub use set_review_grace_period::*;
+pub use set_review_limits::*;
+pub use set_review_reward::*;
+pub use set_scoring_method::*;
+pub use set_team_lead::*;
+pub use settle_reveals::*;
+pub use sync_metadata::*;
 pub use verify::*;
 pub use update_scores::*;
+pub use withdraw_treasury::*;
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    events::DisputeOpened,
+    state::{Dispute, DisputeStatus, Org, ReviewRecord, Score, PAUSE_REVIEWS},
+};
+
+#[derive(Accounts)]
+#[instruction(index: u32)]
+pub struct OpenDisputeCTX<'info> {
+    #[account(mut)]
+    pub applicant: Signer<'info>,
+    #[account(
+        seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
+        bump,
+    )]
+    pub score: Account<'info, Score>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(
+        seeds = [b"review", score.key().as_ref(), index.to_le_bytes().as_ref()],
+        bump = review_record.bump,
+    )]
+    pub review_record: Account<'info, ReviewRecord>,
+    #[account(
+        init,
+        payer = applicant,
+        seeds = [b"dispute", review_record.key().as_ref()],
+        bump,
+        space = Dispute::SPACE
+    )]
+    pub dispute: Account<'info, Dispute>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn open_dispute(
+    ctx: Context<OpenDisputeCTX>,
+    _index: u32,
+    reason_hash: [u8; 32],
+) -> Result<()> {
+    assert!(!ctx.accounts.org.is_paused(PAUSE_REVIEWS));
+    assert!(!ctx.accounts.review_record.revoked);
+    let clock = Clock::get()?;
+    ctx.accounts.dispute.set_inner(Dispute {
+        review: ctx.accounts.review_record.key(),
+        score: ctx.accounts.score.key(),
+        opened_by: ctx.accounts.applicant.key(),
+        reason_hash,
+        opened_at: clock.unix_timestamp,
+        status: DisputeStatus::Open,
+        resolved_by: Pubkey::default(),
+        resolved_at: 0,
+        resolution_hash: [0; 32],
+        bump: *ctx.bumps.get("dispute").unwrap(),
+    });
+    emit!(DisputeOpened {
+        org: ctx.accounts.org.key(),
+        member: ctx.accounts.applicant.key(),
+        review: ctx.accounts.review_record.key(),
+        reason_hash,
+    });
+    Ok(())
+}
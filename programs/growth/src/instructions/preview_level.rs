@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::MetadataAccount;
+
+use crate::{
+    errors::GrowthError,
+    state::{Org, Score},
+};
+
+#[derive(Accounts)]
+pub struct PreviewLevelCTX<'info> {
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account()]
+    pub applicant: AccountInfo<'info>,
+    #[account(
+        seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
+        bump,
+        constraint = score.is_migrated(&org) @ GrowthError::ScoreNotMigrated,
+    )]
+    pub score: Account<'info, Score>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(constraint = metadata.mint == score.mint)]
+    pub metadata: Account<'info, MetadataAccount>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LevelPreview {
+    pub scores: Vec<f32>,
+    pub standard_errors: Vec<f32>,
+    pub variances: Vec<f32>,
+    pub levels: Vec<u8>,
+    pub potential_levels: Vec<u8>,
+    pub next_levels: Vec<u8>,
+    pub level_wait_remaining: i64,
+    pub min_reviews_shortfall: u16,
+    pub reviews_sent_shortfall: u16,
+    pub collection_verified: bool,
+    pub will_change: bool,
+    pub requires_approval: bool,
+}
+
+pub fn preview_level(ctx: Context<PreviewLevelCTX>) -> Result<LevelPreview> {
+    let org = &ctx.accounts.org;
+    let mut score: Score = (*ctx.accounts.score).clone();
+    let clock = Clock::get()?;
+
+    score.update_scores(org);
+    let potential_levels = score.calculate_potential_level(org);
+    let next_levels = score.calculate_next_level(potential_levels.clone());
+
+    let level_wait_remaining =
+        (score.last_update + org.level_wait as i64 + 1 - clock.unix_timestamp).max(0);
+    let min_reviews_shortfall =
+        (org.min_reviews as u16).saturating_sub(*score.reviews_recieved.iter().max().unwrap_or(&0));
+    let reviews_sent_shortfall = if score.is_promotion(&next_levels) {
+        org.min_reviews_sent
+            .saturating_sub(score.counted_reviews_sent(org, clock.unix_timestamp))
+    } else {
+        0
+    };
+    let collection_verified = matches!(&ctx.accounts.metadata.collection, Some(c) if c.verified);
+    let will_change = score.levels != next_levels
+        && collection_verified
+        && score.level_change_due(org, clock.unix_timestamp)
+        && reviews_sent_shortfall == 0;
+
+    Ok(LevelPreview {
+        scores: score.scores.iter().map(|s| zero_nan(*s)).collect(),
+        standard_errors: score
+            .standard_error(org)
+            .iter()
+            .map(|s| zero_nan(*s))
+            .collect(),
+        variances: score.variance(org).iter().map(|s| zero_nan(*s)).collect(),
+        levels: score.levels,
+        potential_levels,
+        next_levels,
+        level_wait_remaining,
+        min_reviews_shortfall,
+        reviews_sent_shortfall,
+        collection_verified,
+        will_change,
+        requires_approval: org.require_promotion_approval,
+    })
+}
+
+// groups without enough reviews come out as NaN, which borsh refuses to serialize
+fn zero_nan(value: f32) -> f32 {
+    if value.is_nan() {
+        0.0
+    } else {
+        value
+    }
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    events::ManagerReassigned,
+    state::{LevelChange, LevelChangeSource, Org, Score, ScoreHistory},
+    utils::Realloc,
+};
+
+#[derive(Accounts)]
+pub struct ReassignManagerCTX<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account()]
+    pub applicant: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
+        bump,
+    )]
+    pub score: Account<'info, Score>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"history", score.key().as_ref()],
+        bump,
+        space = ScoreHistory::space(&org)
+    )]
+    pub history: Account<'info, ScoreHistory>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn reassign_manager(ctx: Context<ReassignManagerCTX>, manager: Pubkey) -> Result<()> {
+    assert_eq!(
+        ctx.accounts.org.authority.key(),
+        ctx.accounts.authority.key()
+    );
+    let score = &mut ctx.accounts.score;
+    // a default pubkey clears the manager
+    assert_ne!(manager, score.applicant);
+    assert_ne!(manager, score.manager);
+    let old_manager = std::mem::replace(&mut score.manager, manager);
+
+    let history = &mut ctx.accounts.history;
+    history.ensure_initialized(score.key(), *ctx.bumps.get("history").unwrap());
+    // histories created before manager changes were recorded are sized for entries
+    // without the two pubkeys, grow them before the first one goes in
+    let space = ScoreHistory::space(&ctx.accounts.org);
+    let data_len = history.to_account_info().data_len();
+    if data_len < space {
+        history.realloc(
+            space - data_len,
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?;
+    }
+    history.record(LevelChange {
+        timestamp: Clock::get()?.unix_timestamp,
+        old_levels: score.levels.clone(),
+        new_levels: score.levels.clone(),
+        source: LevelChangeSource::ReassignManager {
+            old_manager,
+            new_manager: manager,
+        },
+        signer: ctx.accounts.authority.key(),
+    });
+    emit!(ManagerReassigned {
+        org: ctx.accounts.org.key(),
+        member: score.applicant,
+        old_manager,
+        new_manager: manager,
+    });
+    Ok(())
+}
@@ -1,11 +1,19 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    metadata::{update_metadata_accounts_v2, MetadataAccount, UpdateMetadataAccountsV2},
-    token::Token,
+    metadata::MetadataAccount,
+    token::{Token, TokenAccount},
 };
-use mpl_token_metadata::state::Collection;
 
-use crate::state::{Org, Score};
+use crate::{
+    errors::GrowthError,
+    events::ScoreReceived,
+    state::{
+        enforce_review_limits, LevelChange, LevelChangeSource, Org, PendingPromotion, Relationship,
+        ReviewCounter, ReviewRecord, RewardClaims, Score, ScoreHistory, SubmittedReview, Team,
+        PAUSE_LEVEL_CHANGES, PAUSE_REVIEWS,
+    },
+    utils::{pay_level_rewards, sync_team, update_score_uri, RewardAccounts},
+};
 
 #[derive(Accounts)]
 pub struct ScoreCTX<'info> {
@@ -18,101 +26,305 @@ pub struct ScoreCTX<'info> {
         mut,
         seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
         bump,
+        constraint = score.is_migrated(&org) @ GrowthError::ScoreNotMigrated,
     )]
     pub score: Account<'info, Score>,
     #[account()]
     pub org: Account<'info, Org>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"history", score.key().as_ref()],
+        bump,
+        space = ScoreHistory::space(&org)
+    )]
+    pub history: Account<'info, ScoreHistory>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [
+            b"reviewer_counter",
+            org.key().as_ref(),
+            reviewer_score.as_ref().map_or(authority.key, |s| &s.applicant).as_ref()
+        ],
+        bump,
+        space = ReviewCounter::SPACE
+    )]
+    pub reviewer_counter: Account<'info, ReviewCounter>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [
+            b"pair_counter",
+            org.key().as_ref(),
+            reviewer_score.as_ref().map_or(authority.key, |s| &s.applicant).as_ref(),
+            applicant.key().as_ref()
+        ],
+        bump,
+        space = ReviewCounter::SPACE
+    )]
+    pub pair_counter: Account<'info, ReviewCounter>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"promotion", score.key().as_ref()],
+        bump,
+        space = PendingPromotion::space(&org)
+    )]
+    pub promotion: Option<Account<'info, PendingPromotion>>,
+    #[account(
+        mut,
+        seeds = [b"score", org.key().as_ref(), reviewer_score.applicant.as_ref()],
+        bump = reviewer_score.bump,
+        constraint = reviewer_score.applicant != applicant.key(),
+    )]
+    pub reviewer_score: Option<Account<'info, Score>>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [
+            b"review",
+            score.key().as_ref(),
+            score.review_count.to_le_bytes().as_ref()
+        ],
+        bump,
+        space = ReviewRecord::space(&org)
+    )]
+    pub review_record: Account<'info, ReviewRecord>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"reward_claims", score.key().as_ref()],
+        bump,
+        space = RewardClaims::space(&org)
+    )]
+    pub reward_claims: Option<Account<'info, RewardClaims>>,
+    #[account(mut, token::mint = org.reward_mint, token::authority = org)]
+    pub reward_vault: Option<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = org.reward_mint,
+        associated_token::authority = applicant
+    )]
+    pub member_token_account: Option<Account<'info, TokenAccount>>,
     #[account(mut)]
+    pub team: Option<Account<'info, Team>>,
+    #[account(mut, constraint = metadata.mint == score.mint)]
     pub metadata: Account<'info, MetadataAccount>,
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
-    /// CHECK: This is not dangerous because we don't read or write from this account
+    /// CHECK: The org PDA signs the metadata update, so only the real program is accepted
+    #[account(address = mpl_token_metadata::ID)]
     pub token_metadata_program: UncheckedAccount<'info>,
 }
 
-pub fn receive_score(ctx: Context<ScoreCTX>, scores: Vec<f32>, timestamp_override: i64) -> Result<()> {
+pub fn receive_score(
+    ctx: Context<ScoreCTX>,
+    scores: Vec<f32>,
+    timestamp_override: i64,
+    relationship: Relationship,
+) -> Result<()> {
+    assert!(!ctx.accounts.org.is_paused(PAUSE_REVIEWS));
     let score = &mut ctx.accounts.score;
+    assert!(!score.pending);
+    let history = &mut ctx.accounts.history;
+    history.ensure_initialized(score.key(), *ctx.bumps.get("history").unwrap());
     let clock = Clock::get()?;
     let mut submission_ts = clock.unix_timestamp;
     if timestamp_override != 0 {
+        // historical import: only by the org authority with imports enabled,
+        // within the backdate window, never in the future and never before an
+        // earlier import for the same member
+        let org = &ctx.accounts.org;
+        assert!(org.import_enabled && org.max_backdate > 0);
+        assert_eq!(org.authority.key(), ctx.accounts.authority.key());
+        assert!(timestamp_override <= clock.unix_timestamp);
+        assert!(timestamp_override >= clock.unix_timestamp - org.max_backdate);
+        assert!(timestamp_override >= score.last_override_ts);
+        score.last_override_ts = timestamp_override;
         submission_ts = timestamp_override;
     }
     assert_eq!(ctx.accounts.org.weights.len(), scores.len());
-    assert_eq!(
-        ctx.accounts.org.authority.key(),
-        ctx.accounts.authority.key()
+    // managers and team leads review their own reports, everything else goes
+    // through the authority
+    let by_authority = ctx.accounts.org.authority.key() == ctx.accounts.authority.key();
+    let by_lead =
+        ctx.accounts.team.as_ref().is_some_and(|team| {
+            team.key() == score.team && team.lead == ctx.accounts.authority.key()
+        });
+    assert!(by_authority || by_lead || score.is_manager(ctx.accounts.authority.key));
+    ctx.accounts.reviewer_counter.bump = *ctx.bumps.get("reviewer_counter").unwrap();
+    ctx.accounts.pair_counter.bump = *ctx.bumps.get("pair_counter").unwrap();
+    enforce_review_limits(
+        &ctx.accounts.org,
+        &mut ctx.accounts.reviewer_counter,
+        &mut ctx.accounts.pair_counter,
+        clock.unix_timestamp,
     );
 
-    for (p1, e1) in scores.iter().enumerate() {
-        score.scores_sum[p1] += e1;
-        if *e1 != 0 as f32 {
-            score.reviews_recieved[p1] += 1;
-        }
+    // the authority can submit on behalf of a member, whose score then counts the
+    // review as sent and weights it
+    let mut reviewer = ctx.accounts.authority.key();
+    let mut reviewer_levels = None;
+    let sent_counted = ctx.accounts.reviewer_score.is_some();
+    if let Some(reviewer_score) = ctx.accounts.reviewer_score.as_mut() {
+        assert!(by_authority || reviewer_score.applicant == reviewer);
+        reviewer_score.record_review_sent(&ctx.accounts.org, clock.unix_timestamp);
+        reviewer = reviewer_score.applicant;
+        reviewer_levels = Some(reviewer_score.levels.clone());
     }
+    // a self assessment is the member's own review, relayed by whoever may sign for
+    // them. It doesn't count as sent, so no `reviewer_score` next to the member's score.
+    if relationship == Relationship::SelfAssessment {
+        assert!(!sent_counted);
+        reviewer = score.applicant;
+        reviewer_levels = Some(score.levels.clone());
+    } else {
+        assert_ne!(reviewer, score.applicant);
+    }
+    let relationship = score.review_relationship(&reviewer, relationship);
+    let review = SubmittedReview {
+        weights: ctx
+            .accounts
+            .org
+            .review_weights(reviewer_levels.as_deref(), relationship),
+        scores,
+        relationship,
+    };
 
-    let next_level = score.reconcile(&ctx.accounts.org);
+    let review_record = &mut ctx.accounts.review_record;
+    review_record.set_inner(ReviewRecord::new(
+        score,
+        reviewer,
+        &review,
+        submission_ts,
+        clock.unix_timestamp,
+        *ctx.bumps.get("review_record").unwrap(),
+    ));
+    review_record.sent_counted = sent_counted;
+    score.review_count += 1;
 
-    if ctx.accounts.metadata.collection.as_ref().unwrap().verified == false {
+    let promotion_bump = ctx.bumps.get("promotion").copied();
+    let token_program = ctx.accounts.token_program.to_account_info();
+    settle_review(
+        ReviewAccounts {
+            score,
+            org: &ctx.accounts.org,
+            history,
+            promotion: ctx.accounts.promotion.as_mut(),
+            promotion_bump,
+            team: ctx.accounts.team.as_mut(),
+            metadata: &ctx.accounts.metadata,
+            token_metadata_program: &ctx.accounts.token_metadata_program,
+            rewards: RewardAccounts::from_optional(
+                ctx.accounts.reward_claims.as_mut(),
+                ctx.bumps.get("reward_claims").copied(),
+                ctx.accounts.reward_vault.as_ref(),
+                ctx.accounts.member_token_account.as_ref(),
+                token_program,
+            ),
+        },
+        review,
+        ctx.accounts.authority.key(),
+        LevelChangeSource::ReceiveScore,
+        submission_ts,
+        timestamp_override != 0,
+    )
+}
+
+pub struct ReviewAccounts<'a, 'info> {
+    pub score: &'a mut Account<'info, Score>,
+    pub org: &'a Account<'info, Org>,
+    pub history: &'a mut Account<'info, ScoreHistory>,
+    pub promotion: Option<&'a mut Account<'info, PendingPromotion>>,
+    pub promotion_bump: Option<u8>,
+    pub team: Option<&'a mut Account<'info, Team>>,
+    pub metadata: &'a Account<'info, MetadataAccount>,
+    pub token_metadata_program: &'a AccountInfo<'info>,
+    pub rewards: Option<RewardAccounts<'a, 'info>>,
+}
+
+pub fn settle_review(
+    accounts: ReviewAccounts,
+    review: SubmittedReview,
+    signer: Pubkey,
+    source: LevelChangeSource,
+    submission_ts: i64,
+    backdated: bool,
+) -> Result<()> {
+    let ReviewAccounts {
+        score,
+        org,
+        history,
+        promotion,
+        promotion_bump,
+        mut team,
+        metadata,
+        token_metadata_program,
+        rewards,
+    } = accounts;
+    let clock = Clock::get()?;
+
+    score.record_review(&review.scores, &review.weights);
+    score.record_relationship_review(review.relationship, &review.scores);
+    emit!(ScoreReceived {
+        org: org.key(),
+        member: score.applicant,
+        signer,
+        scores: review.scores,
+        relationship: review.relationship,
+        submission_ts,
+        backdated,
+    });
+
+    let old_scores = score.scores.clone();
+    let next_level = score.reconcile(org);
+    sync_team(org, score, team.as_deref_mut(), &old_scores, &score.levels)?;
+
+    if !metadata.collection.as_ref().unwrap().verified {
         return Ok(());
     }
 
     msg!(
         "check debug last_update:{} level_wait:{} current_ts:{} current_level:{:?} next_level:{:?}",
         score.last_update,
-        ctx.accounts.org.level_wait,
+        org.level_wait,
         clock.unix_timestamp,
         score.levels,
         next_level
     );
     if score.levels == next_level {
         score.last_update = clock.unix_timestamp;
-    } else if score.levels != next_level
-        && score.last_update + (ctx.accounts.org.level_wait as i64) < submission_ts
-        && *score.reviews_recieved.iter().max().unwrap() >= ctx.accounts.org.min_reviews as u16
-    {
-        score.levels = next_level;
-        score.last_update = submission_ts;
-
-        let mut level_string: String = score
-            .levels
-            .iter()
-            .map(|&id| id.to_string() + "-")
-            .collect();
-        level_string.pop();
-        let mut uri = ctx.accounts.org.domain.clone();
-        uri.push_str("/");
-        uri.push_str(&level_string);
-        uri.push_str(".json");
-
-        msg!("Updating NFT");
-        let org_mint = ctx.accounts.org.mint.key();
-        let signer: &[&[&[u8]]] = &[&[
-            b"org",
-            org_mint.as_ref(),
-            ctx.accounts.authority.key.as_ref(),
-            &[ctx.accounts.org.bump],
-        ]];
-        let data_v2 = mpl_token_metadata::state::DataV2 {
-            name: ctx.accounts.metadata.data.name.to_string(),
-            symbol: "SCORE".to_string(),
-            uri,
-            seller_fee_basis_points: 0,
-            creators: None,
-            collection: Some(Collection {
-                verified: true,
-                key: ctx.accounts.org.mint.key(),
-            }),
-            uses: None,
-        };
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_metadata_program.to_account_info(),
-            UpdateMetadataAccountsV2 {
-                metadata: ctx.accounts.metadata.to_account_info(),
-                update_authority: ctx.accounts.org.to_account_info(),
-            },
+    } else if org.is_paused(PAUSE_LEVEL_CHANGES) {
+        msg!("Level changes are paused");
+    } else if !score.can_change_to(org, &next_level, clock.unix_timestamp) {
+        msg!("Not enough reviews sent for a promotion");
+    } else if score.level_change_due(org, submission_ts) {
+        if org.require_promotion_approval {
+            promotion
+                .ok_or(GrowthError::PromotionAccountMissing)?
+                .request(
+                    score.key(),
+                    score.levels.clone(),
+                    next_level,
+                    submission_ts,
+                    signer,
+                    promotion_bump.unwrap(),
+                );
+            return Ok(());
+        }
+        history.record(LevelChange {
+            timestamp: submission_ts,
+            old_levels: score.levels.clone(),
+            new_levels: next_level.clone(),
+            source,
             signer,
-        );
-        update_metadata_accounts_v2(cpi_ctx, None, Some(data_v2), Some(true), Some(true))?;
+        });
+        let old_levels = std::mem::replace(&mut score.levels, next_level);
+        score.last_update = submission_ts;
+        sync_team(org, score, team, &score.scores, &old_levels)?;
+        update_score_uri(org, metadata, token_metadata_program, &score.levels)?;
+        pay_level_rewards(org, score, rewards, &old_levels)?;
     }
     Ok(())
 }
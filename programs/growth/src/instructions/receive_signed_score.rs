@@ -0,0 +1,253 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use anchor_spl::{
+    metadata::MetadataAccount,
+    token::{Token, TokenAccount},
+};
+
+use super::{settle_review, ReviewAccounts};
+use crate::{
+    errors::GrowthError,
+    state::{
+        enforce_review_limits, LevelChangeSource, Org, PendingPromotion, Relationship,
+        ReviewCounter, ReviewRecord, RewardClaims, Score, ScoreHistory, SubmittedReview, Team,
+        PAUSE_REVIEWS,
+    },
+    utils::{verify_ed25519_ix, RewardAccounts},
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SignedReview {
+    pub org: Pubkey,
+    pub applicant: Pubkey,
+    pub scores: Vec<f32>,
+    pub nonce: u64,
+    pub relationship: Relationship,
+}
+
+#[derive(Accounts)]
+pub struct SignedScoreCTX<'info> {
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    /// CHECK: The reviewer's signature is checked through the Ed25519 instruction
+    #[account()]
+    pub reviewer: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"score", org.key().as_ref(), reviewer.key().as_ref()],
+        bump,
+    )]
+    pub reviewer_score: Option<Account<'info, Score>>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account()]
+    pub applicant: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
+        bump,
+        constraint = score.is_migrated(&org) @ GrowthError::ScoreNotMigrated,
+    )]
+    pub score: Account<'info, Score>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        seeds = [b"history", score.key().as_ref()],
+        bump,
+        space = ScoreHistory::space(&org)
+    )]
+    pub history: Account<'info, ScoreHistory>,
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        seeds = [b"reviewer_counter", org.key().as_ref(), reviewer.key().as_ref()],
+        bump,
+        space = ReviewCounter::SPACE
+    )]
+    pub reviewer_counter: Account<'info, ReviewCounter>,
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        seeds = [
+            b"pair_counter",
+            org.key().as_ref(),
+            reviewer.key().as_ref(),
+            applicant.key().as_ref()
+        ],
+        bump,
+        space = ReviewCounter::SPACE
+    )]
+    pub pair_counter: Account<'info, ReviewCounter>,
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        seeds = [b"promotion", score.key().as_ref()],
+        bump,
+        space = PendingPromotion::space(&org)
+    )]
+    pub promotion: Option<Account<'info, PendingPromotion>>,
+    #[account(
+        init,
+        payer = relayer,
+        seeds = [
+            b"review",
+            score.key().as_ref(),
+            score.review_count.to_le_bytes().as_ref()
+        ],
+        bump,
+        space = ReviewRecord::space(&org)
+    )]
+    pub review_record: Account<'info, ReviewRecord>,
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        seeds = [b"reward_claims", score.key().as_ref()],
+        bump,
+        space = RewardClaims::space(&org)
+    )]
+    pub reward_claims: Option<Account<'info, RewardClaims>>,
+    #[account(mut, token::mint = org.reward_mint, token::authority = org)]
+    pub reward_vault: Option<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = org.reward_mint,
+        associated_token::authority = applicant
+    )]
+    pub member_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub team: Option<Account<'info, Team>>,
+    #[account(mut, constraint = metadata.mint == score.mint)]
+    pub metadata: Account<'info, MetadataAccount>,
+    /// CHECK: Instructions sysvar, used to find the Ed25519 instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    /// CHECK: The org PDA signs the metadata update, so only the real program is accepted
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+}
+
+pub fn receive_signed_score(
+    ctx: Context<SignedScoreCTX>,
+    scores: Vec<f32>,
+    nonce: u64,
+    relationship: Relationship,
+) -> Result<()> {
+    assert!(!ctx.accounts.org.is_paused(PAUSE_REVIEWS));
+    // members review through commit_review / reveal_review in commit-reveal mode
+    assert!(!ctx.accounts.org.commit_reveal);
+    assert_eq!(ctx.accounts.org.weights.len(), scores.len());
+    // only a self assessment is signed by the member, with their own score holding the
+    // nonce. It doesn't count as sent, so no `reviewer_score` next to the member's score.
+    let self_assessment = relationship == Relationship::SelfAssessment;
+    assert_eq!(
+        self_assessment,
+        ctx.accounts.applicant.key() == ctx.accounts.reviewer.key()
+    );
+    let reviewer_score = if self_assessment {
+        assert!(ctx.accounts.reviewer_score.is_none());
+        &mut ctx.accounts.score
+    } else {
+        ctx.accounts
+            .reviewer_score
+            .as_mut()
+            .ok_or(GrowthError::ReviewerScoreMissing)?
+    };
+    assert!(!reviewer_score.pending);
+    assert!(nonce > reviewer_score.review_nonce);
+    reviewer_score.review_nonce = nonce;
+    let reviewer_levels = reviewer_score.levels.clone();
+
+    // the reviewer signs the review off-chain, the Ed25519 instruction right before
+    // this one proves it
+    let message = SignedReview {
+        org: ctx.accounts.org.key(),
+        applicant: ctx.accounts.applicant.key(),
+        scores: scores.clone(),
+        nonce,
+        relationship,
+    }
+    .try_to_vec()?;
+    let current_index = load_current_index_checked(&ctx.accounts.instructions)?;
+    assert!(current_index > 0);
+    let ed25519_ix =
+        load_instruction_at_checked((current_index - 1) as usize, &ctx.accounts.instructions)?;
+    assert!(verify_ed25519_ix(
+        &ed25519_ix,
+        ctx.accounts.reviewer.key,
+        &message
+    ));
+
+    let now = Clock::get()?.unix_timestamp;
+    let org = &ctx.accounts.org;
+    if let Some(reviewer_score) = ctx.accounts.reviewer_score.as_mut() {
+        reviewer_score.record_review_sent(org, now);
+    }
+    ctx.accounts.reviewer_counter.bump = *ctx.bumps.get("reviewer_counter").unwrap();
+    ctx.accounts.pair_counter.bump = *ctx.bumps.get("pair_counter").unwrap();
+    enforce_review_limits(
+        &ctx.accounts.org,
+        &mut ctx.accounts.reviewer_counter,
+        &mut ctx.accounts.pair_counter,
+        now,
+    );
+
+    let score_key = ctx.accounts.score.key();
+    ctx.accounts
+        .history
+        .ensure_initialized(score_key, *ctx.bumps.get("history").unwrap());
+    let relationship = ctx
+        .accounts
+        .score
+        .review_relationship(ctx.accounts.reviewer.key, relationship);
+    let review = SubmittedReview {
+        weights: ctx
+            .accounts
+            .org
+            .review_weights(Some(&reviewer_levels), relationship),
+        scores,
+        relationship,
+    };
+    let score = &mut ctx.accounts.score;
+    let review_record = &mut ctx.accounts.review_record;
+    review_record.set_inner(ReviewRecord::new(
+        score,
+        ctx.accounts.reviewer.key(),
+        &review,
+        now,
+        now,
+        *ctx.bumps.get("review_record").unwrap(),
+    ));
+    review_record.sent_counted = !self_assessment;
+    score.review_count += 1;
+    let promotion_bump = ctx.bumps.get("promotion").copied();
+    let token_program = ctx.accounts.token_program.to_account_info();
+    settle_review(
+        ReviewAccounts {
+            score: &mut ctx.accounts.score,
+            org: &ctx.accounts.org,
+            history: &mut ctx.accounts.history,
+            promotion: ctx.accounts.promotion.as_mut(),
+            promotion_bump,
+            team: ctx.accounts.team.as_mut(),
+            metadata: &ctx.accounts.metadata,
+            token_metadata_program: &ctx.accounts.token_metadata_program,
+            rewards: RewardAccounts::from_optional(
+                ctx.accounts.reward_claims.as_mut(),
+                ctx.bumps.get("reward_claims").copied(),
+                ctx.accounts.reward_vault.as_ref(),
+                ctx.accounts.member_token_account.as_ref(),
+                token_program,
+            ),
+        },
+        review,
+        ctx.accounts.reviewer.key(),
+        LevelChangeSource::SignedReview,
+        now,
+        false,
+    )
+}
@@ -1,34 +1,61 @@
 use anchor_lang::prelude::*;
-use anchor_spl::metadata::{
-    create_metadata_accounts_v3, CreateMetadataAccountsV3, MasterEditionAccount,
-};
-use anchor_spl::token::{mint_to, Mint, MintTo, Token, TokenAccount};
-use mpl_token_metadata::state::{Collection, DataV2};
+use anchor_spl::metadata::MasterEditionAccount;
+use anchor_spl::token::{Mint, Token, TokenAccount};
 
-use crate::state::Org;
+use crate::state::{FeePayer, Org, Treasury, PAUSE_REGISTRATIONS};
 use crate::state::Score;
-use crate::utils::Realloc;
+use crate::state::ScoreHistory;
+use crate::utils::{collect_registration_fee, create_score_nft, FeeAccounts, Realloc, ScoreNftAccounts};
 
 #[derive(Accounts)]
 pub struct RegisterCTX<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
-    /// CHECK: This is not dangerous because we don't read or write from this account
-    #[account()]
-    pub applicant: AccountInfo<'info>,
+    #[account(mut)]
+    pub applicant: Signer<'info>,
     #[account()]
     pub org: Account<'info, Org>,
     #[account(
-        init_if_needed,
+        init,
         payer = authority,
         seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
         bump,
         space= 8 + std::mem::size_of::<Score>()
     )]
     pub score: Account<'info, Score>,
-    #[account(mut)]
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"history", score.key().as_ref()],
+        bump,
+        space = ScoreHistory::space(&org)
+    )]
+    pub history: Account<'info, ScoreHistory>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"treasury", org.key().as_ref()],
+        bump,
+        space = Treasury::SPACE
+    )]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut, token::mint = org.fee_mint)]
+    pub fee_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(mut, token::mint = org.fee_mint, token::authority = treasury)]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        mint::decimals = 0,
+        mint::authority = org,
+        mint::freeze_authority = org,
+        constraint = register_mint.supply == 0,
+    )]
     pub register_mint: Account<'info, Mint>,
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = token_account.mint == register_mint.key(),
+        constraint = token_account.owner == applicant.key(),
+    )]
     pub token_account: Account<'info, TokenAccount>,
     /// CHECK: This is not dangerous because we don't read or write from this account
     #[account(mut)]
@@ -50,100 +77,74 @@ pub fn register(
     levels: Vec<u8>,
     last_update: i64,
 ) -> Result<()> {
+    assert!(!ctx.accounts.org.is_paused(PAUSE_REGISTRATIONS));
     assert_eq!(
         ctx.accounts.org.authority.key(),
         ctx.accounts.authority.key()
     );
     assert_eq!(levels.len(), ctx.accounts.org.levels.len());
-    let org = &mut ctx.accounts.org;
-    let mint = org.mint.key();
-    let signer: &[&[&[u8]]] = &[&[
-        b"org",
-        mint.as_ref(),
-        ctx.accounts.authority.key.as_ref(),
-        &[org.bump],
-    ]];
+    let payer = match ctx.accounts.org.fee_payer {
+        FeePayer::Authority => ctx.accounts.authority.to_account_info(),
+        FeePayer::Applicant => ctx.accounts.applicant.to_account_info(),
+    };
+    collect_registration_fee(
+        &ctx.accounts.org,
+        ctx.accounts.applicant.key(),
+        FeeAccounts {
+            payer,
+            treasury: &mut ctx.accounts.treasury,
+            treasury_bump: *ctx.bumps
+                .get("treasury")
+                .expect("Failed to fetch bump for `treasury`"),
+            fee_token_account: ctx.accounts.fee_token_account.as_ref(),
+            treasury_token_account: ctx.accounts.treasury_token_account.as_ref(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+    )?;
+    let org = &ctx.accounts.org;
 
     ctx.accounts.score.set_inner(Score::new(
         *ctx.bumps
             .get("score")
             .expect("Failed to fetch bump for `score`"),
     ));
-    let space_to_add = 4 * (org.ranges.len() + 1) // scores
-    + (4 * org.weights.len())  //scores_sum
-    + org.levels.len() // levels
-    + (name.len() * 4) // name
-    + (2 * org.weights.len());
+    let score_key = ctx.accounts.score.key();
+    ctx.accounts.history.ensure_initialized(
+        score_key,
+        *ctx.bumps
+            .get("history")
+            .expect("Failed to fetch bump for `history`"),
+    );
+    let space_to_add = Score::space_to_add(org, &name);
     msg!("space to add:{}", space_to_add);
     ctx.accounts.score.realloc(
         space_to_add,
         &ctx.accounts.authority,
         &ctx.accounts.system_program,
     )?;
-    ctx.accounts.score.scores = vec![0 as f32; org.ranges.len() + 1];
-    ctx.accounts.score.levels = levels;
-    ctx.accounts.score.reviews_recieved = vec![0 as u16; org.weights.len()];
-    ctx.accounts.score.name = name.clone();
-    ctx.accounts.score.last_update = last_update;
-    msg!("last update:{}", last_update);
-    ctx.accounts.score.applicant = ctx.accounts.applicant.key();
-    ctx.accounts.score.mint = ctx.accounts.register_mint.key();
-    ctx.accounts.score.scores_sum = vec![0 as f32; org.weights.len()];
-
-    msg!("Minting token");
-    let mint_to_cpi_accounts = MintTo {
-        mint: ctx.accounts.register_mint.to_account_info(),
-        to: ctx.accounts.token_account.to_account_info(),
-        authority: org.to_account_info(),
-    };
-    let token_program = ctx.accounts.token_program.to_account_info();
-    let mint_to_cpi_ctx = CpiContext::new_with_signer(token_program, mint_to_cpi_accounts, signer);
-    mint_to(mint_to_cpi_ctx, 1)?;
-    msg!("Token minted");
-
-    let mut metadata_name = org.name.clone();
-    metadata_name.push_str(" - ");
-    metadata_name.push_str(&name);
-    let mut level_string: String = ctx
-        .accounts
-        .score
-        .levels
-        .iter()
-        .map(|&id| id.to_string() + "-")
-        .collect();
-    level_string.pop();
-    let mut uri = org.domain.clone();
-    uri.push_str("/");
-    uri.push_str(&level_string);
-    uri.push_str(".json");
-    let data_v2 = DataV2 {
-        name: metadata_name.to_string(),
-        symbol: "SCORE".to_string(),
-        uri,
-        seller_fee_basis_points: 0,
-        creators: None,
-        collection: Some(Collection {
-            verified: false,
-            key: org.mint.key(),
-        }),
-        uses: None,
-    };
-    let create_metadata_cpi_accounts = CreateMetadataAccountsV3 {
-        metadata: ctx.accounts.metadata.to_account_info(),
-        mint: ctx.accounts.register_mint.to_account_info(),
-        mint_authority: org.to_account_info(),
-        update_authority: org.to_account_info(),
-        payer: ctx.accounts.authority.to_account_info(),
-        system_program: ctx.accounts.system_program.to_account_info(),
-        rent: ctx.accounts.rent.to_account_info(),
-    };
-    let create_metadata_cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_metadata_program.to_account_info(),
-        create_metadata_cpi_accounts,
-        signer,
+    ctx.accounts.score.init_member(
+        org,
+        name.clone(),
+        ctx.accounts.applicant.key(),
+        levels,
+        last_update,
     );
+    ctx.accounts.score.mint = ctx.accounts.register_mint.key();
 
-    create_metadata_accounts_v3(create_metadata_cpi_ctx, data_v2, true, true, None)?;
-    msg!("Metadata Account Created !!!");
-    Ok(())
+    create_score_nft(
+        org,
+        ScoreNftAccounts {
+            payer: ctx.accounts.authority.to_account_info(),
+            register_mint: ctx.accounts.register_mint.to_account_info(),
+            token_account: ctx.accounts.token_account.to_account_info(),
+            metadata: ctx.accounts.metadata.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        },
+        &name,
+        &ctx.accounts.score.levels,
+    )
 }
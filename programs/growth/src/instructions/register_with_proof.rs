@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::state::{Org, Score, ScoreHistory, Treasury, PAUSE_REGISTRATIONS};
+use crate::utils::{
+    collect_registration_fee, create_score_nft, hash_leaf, verify_proof, FeeAccounts, Realloc,
+    ScoreNftAccounts,
+};
+
+#[derive(Accounts)]
+pub struct RegisterWithProofCTX<'info> {
+    #[account(mut)]
+    pub applicant: Signer<'info>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(
+        init,
+        payer = applicant,
+        seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
+        bump,
+        space = 8 + std::mem::size_of::<Score>()
+    )]
+    pub score: Account<'info, Score>,
+    #[account(
+        init,
+        payer = applicant,
+        seeds = [b"history", score.key().as_ref()],
+        bump,
+        space = ScoreHistory::space(&org)
+    )]
+    pub history: Account<'info, ScoreHistory>,
+    #[account(
+        init_if_needed,
+        payer = applicant,
+        seeds = [b"treasury", org.key().as_ref()],
+        bump,
+        space = Treasury::SPACE
+    )]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut, token::mint = org.fee_mint)]
+    pub fee_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(mut, token::mint = org.fee_mint, token::authority = treasury)]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        mint::decimals = 0,
+        mint::authority = org,
+        mint::freeze_authority = org,
+        constraint = register_mint.supply == 0,
+    )]
+    pub register_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        constraint = token_account.mint == register_mint.key(),
+        constraint = token_account.owner == applicant.key(),
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    /// CHECK: The org PDA signs the metadata creation, so only the real program is accepted
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub rent: AccountInfo<'info>,
+}
+
+pub fn register_with_proof(
+    ctx: Context<RegisterWithProofCTX>,
+    name: String,
+    levels: Vec<u8>,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    assert!(!ctx.accounts.org.is_paused(PAUSE_REGISTRATIONS));
+    let org = &ctx.accounts.org;
+    assert_ne!(org.allowlist_root, [0; 32]);
+    // an allowlist built without starting levels hashes the wallet alone
+    let leaf = hash_leaf(&[ctx.accounts.applicant.key.as_ref(), &levels]);
+    assert!(verify_proof(&proof, org.allowlist_root, leaf));
+    let levels = if levels.is_empty() {
+        vec![0; org.levels.len()]
+    } else {
+        levels
+    };
+    assert_eq!(levels.len(), org.levels.len());
+    // self-service registrations are always paid by the applicant, the only signer
+    collect_registration_fee(
+        &ctx.accounts.org,
+        ctx.accounts.applicant.key(),
+        FeeAccounts {
+            payer: ctx.accounts.applicant.to_account_info(),
+            treasury: &mut ctx.accounts.treasury,
+            treasury_bump: *ctx.bumps.get("treasury").unwrap(),
+            fee_token_account: ctx.accounts.fee_token_account.as_ref(),
+            treasury_token_account: ctx.accounts.treasury_token_account.as_ref(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+    )?;
+
+    ctx.accounts
+        .score
+        .set_inner(Score::new(*ctx.bumps.get("score").unwrap()));
+    let score_key = ctx.accounts.score.key();
+    ctx.accounts
+        .history
+        .ensure_initialized(score_key, *ctx.bumps.get("history").unwrap());
+    let space_to_add = Score::space_to_add(org, &name);
+    ctx.accounts.score.realloc(
+        space_to_add,
+        &ctx.accounts.applicant,
+        &ctx.accounts.system_program,
+    )?;
+    ctx.accounts.score.init_member(
+        org,
+        name.clone(),
+        ctx.accounts.applicant.key(),
+        levels,
+        Clock::get()?.unix_timestamp,
+    );
+    ctx.accounts.score.mint = ctx.accounts.register_mint.key();
+
+    create_score_nft(
+        org,
+        ScoreNftAccounts {
+            payer: ctx.accounts.applicant.to_account_info(),
+            register_mint: ctx.accounts.register_mint.to_account_info(),
+            token_account: ctx.accounts.token_account.to_account_info(),
+            metadata: ctx.accounts.metadata.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        },
+        &name,
+        &ctx.accounts.score.levels,
+    )
+}
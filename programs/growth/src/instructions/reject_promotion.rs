@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Org, PendingPromotion, PromotionStatus, Score, PAUSE_LEVEL_CHANGES};
+
+#[derive(Accounts)]
+pub struct RejectPromotionCTX<'info> {
+    pub approver: Signer<'info>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account()]
+    pub applicant: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
+        bump,
+    )]
+    pub score: Account<'info, Score>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(address = org.parent)]
+    pub parent: Option<Account<'info, Org>>,
+    #[account(
+        mut,
+        seeds = [b"promotion", score.key().as_ref()],
+        bump = promotion.bump,
+    )]
+    pub promotion: Account<'info, PendingPromotion>,
+}
+
+pub fn reject_promotion(ctx: Context<RejectPromotionCTX>, reason_hash: [u8; 32]) -> Result<()> {
+    assert!(!ctx.accounts.org.is_paused(PAUSE_LEVEL_CHANGES));
+    let promotion = &mut ctx.accounts.promotion;
+    let clock = Clock::get()?;
+    assert!(
+        ctx.accounts
+            .org
+            .can_approve_promotions(ctx.accounts.approver.key, ctx.accounts.parent.as_ref())
+            || ctx.accounts.score.is_manager(ctx.accounts.approver.key)
+    );
+    assert_eq!(promotion.status, PromotionStatus::Pending);
+
+    msg!(
+        "promotion rejected {:?} -> {:?}",
+        promotion.old_levels,
+        promotion.new_levels
+    );
+    promotion.status = PromotionStatus::Rejected;
+    promotion.reviewed_by = ctx.accounts.approver.key();
+    promotion.reviewed_at = clock.unix_timestamp;
+    promotion.reason_hash = reason_hash;
+    // the member has to sit out another `level_wait` before the next request
+    ctx.accounts.score.last_update = clock.unix_timestamp;
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+use super::TeamMemberCTX;
+
+pub fn remove_team_member(ctx: Context<TeamMemberCTX>) -> Result<()> {
+    assert_eq!(
+        ctx.accounts.org.authority.key(),
+        ctx.accounts.authority.key()
+    );
+    let score = &mut ctx.accounts.score;
+    assert_eq!(score.team, ctx.accounts.team.key());
+    score.team = Pubkey::default();
+    ctx.accounts
+        .team
+        .aggregate
+        .remove_member(&ctx.accounts.org, &score.scores, &score.levels)
+}
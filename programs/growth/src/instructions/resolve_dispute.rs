@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+
+use super::{apply_revocation, ensure_reveal_settled};
+use crate::{
+    errors::GrowthError,
+    events::DisputeResolved,
+    state::{
+        Dispute, DisputeStatus, Org, RevealBatch, ReviewRecord, ReviewerRewards, Score, Team,
+        PAUSE_REVIEWS,
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(index: u32)]
+pub struct ResolveDisputeCTX<'info> {
+    pub authority: Signer<'info>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account()]
+    pub applicant: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
+        bump,
+        constraint = score.is_migrated(&org) @ GrowthError::ScoreNotMigrated,
+    )]
+    pub score: Account<'info, Score>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(address = org.parent)]
+    pub parent: Option<Account<'info, Org>>,
+    #[account(
+        mut,
+        seeds = [b"review", score.key().as_ref(), index.to_le_bytes().as_ref()],
+        bump = review_record.bump,
+    )]
+    pub review_record: Account<'info, ReviewRecord>,
+    #[account(
+        mut,
+        seeds = [b"dispute", review_record.key().as_ref()],
+        bump = dispute.bump,
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(
+        mut,
+        seeds = [b"reviewer_rewards", org.key().as_ref(), review_record.reviewer.as_ref()],
+        bump = reviewer_rewards.bump,
+    )]
+    pub reviewer_rewards: Option<Account<'info, ReviewerRewards>>,
+    #[account(
+        mut,
+        seeds = [b"score", org.key().as_ref(), review_record.reviewer.as_ref()],
+        bump,
+    )]
+    pub reviewer_score: Option<Account<'info, Score>>,
+    #[account(constraint = reveal_batch.key() == review_record.reveal_batch)]
+    pub reveal_batch: Option<Account<'info, RevealBatch>>,
+    #[account(mut)]
+    pub team: Option<Account<'info, Team>>,
+}
+
+// Upholding a dispute revokes the review.
+pub fn resolve_dispute(
+    ctx: Context<ResolveDisputeCTX>,
+    _index: u32,
+    uphold: bool,
+    resolution_hash: [u8; 32],
+) -> Result<()> {
+    assert!(!ctx.accounts.org.is_paused(PAUSE_REVIEWS));
+    assert!(ctx
+        .accounts
+        .org
+        .can_oversee(ctx.accounts.authority.key, ctx.accounts.parent.as_ref()));
+    assert_eq!(ctx.accounts.dispute.status, DisputeStatus::Open);
+    if uphold && !ctx.accounts.review_record.revoked {
+        ensure_reveal_settled(
+            &ctx.accounts.review_record,
+            ctx.accounts.reveal_batch.as_ref(),
+        )?;
+        apply_revocation(
+            &ctx.accounts.org,
+            &mut ctx.accounts.score,
+            &mut ctx.accounts.review_record,
+            ctx.accounts.reviewer_rewards.as_mut(),
+            ctx.accounts.reviewer_score.as_mut(),
+            ctx.accounts.team.as_mut(),
+            ctx.accounts.authority.key(),
+        )?;
+    }
+    let dispute = &mut ctx.accounts.dispute;
+    dispute.status = if uphold {
+        DisputeStatus::Upheld
+    } else {
+        DisputeStatus::Dismissed
+    };
+    dispute.resolved_by = ctx.accounts.authority.key();
+    dispute.resolved_at = Clock::get()?.unix_timestamp;
+    dispute.resolution_hash = resolution_hash;
+    emit!(DisputeResolved {
+        org: ctx.accounts.org.key(),
+        member: ctx.accounts.applicant.key(),
+        review: ctx.accounts.review_record.key(),
+        upheld: uphold,
+        resolution_hash,
+    });
+    Ok(())
+}
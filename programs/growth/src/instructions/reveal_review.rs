@@ -0,0 +1,188 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::GrowthError,
+    state::{
+        enforce_review_limits, Org, Relationship, RevealBatch, RevealReceipt, ReviewCommitment,
+        ReviewCounter, ReviewRecord, Score, SubmittedReview, PAUSE_REVIEWS,
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(commitment: [u8; 32])]
+pub struct RevealReviewCTX<'info> {
+    #[account(mut)]
+    pub reviewer: Signer<'info>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account()]
+    pub applicant: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
+        bump,
+    )]
+    pub score: Account<'info, Score>,
+    #[account(
+        mut,
+        seeds = [b"score", org.key().as_ref(), reviewer.key().as_ref()],
+        bump,
+        constraint = !reviewer_score.pending,
+    )]
+    pub reviewer_score: Option<Account<'info, Score>>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(
+        init_if_needed,
+        payer = reviewer,
+        seeds = [b"reviewer_counter", org.key().as_ref(), reviewer.key().as_ref()],
+        bump,
+        space = ReviewCounter::SPACE
+    )]
+    pub reviewer_counter: Account<'info, ReviewCounter>,
+    #[account(
+        init_if_needed,
+        payer = reviewer,
+        seeds = [
+            b"pair_counter",
+            org.key().as_ref(),
+            reviewer.key().as_ref(),
+            applicant.key().as_ref()
+        ],
+        bump,
+        space = ReviewCounter::SPACE
+    )]
+    pub pair_counter: Account<'info, ReviewCounter>,
+    #[account(
+        mut,
+        seeds = [
+            b"commitment",
+            org.key().as_ref(),
+            reviewer.key().as_ref(),
+            commitment.as_ref()
+        ],
+        bump = review_commitment.bump,
+    )]
+    pub review_commitment: Account<'info, ReviewCommitment>,
+    #[account(
+        init,
+        payer = reviewer,
+        seeds = [
+            b"reveal",
+            score.key().as_ref(),
+            reviewer.key().as_ref(),
+            review_commitment.cycle.to_le_bytes().as_ref()
+        ],
+        bump,
+        space = RevealReceipt::SPACE
+    )]
+    pub reveal_receipt: Account<'info, RevealReceipt>,
+    #[account(
+        init_if_needed,
+        payer = reviewer,
+        seeds = [
+            b"batch",
+            score.key().as_ref(),
+            review_commitment.cycle.to_le_bytes().as_ref()
+        ],
+        bump,
+        space = RevealBatch::space(&org)
+    )]
+    pub reveal_batch: Account<'info, RevealBatch>,
+    #[account(
+        init,
+        payer = reviewer,
+        seeds = [
+            b"review",
+            score.key().as_ref(),
+            score.review_count.to_le_bytes().as_ref()
+        ],
+        bump,
+        space = ReviewRecord::space(&org)
+    )]
+    pub review_record: Account<'info, ReviewRecord>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn reveal_review(
+    ctx: Context<RevealReviewCTX>,
+    commitment: [u8; 32],
+    scores: Vec<f32>,
+    relationship: Relationship,
+    salt: [u8; 32],
+) -> Result<()> {
+    let org = &ctx.accounts.org;
+    assert!(!org.is_paused(PAUSE_REVIEWS));
+    assert!(!ctx.accounts.score.pending);
+    let review_commitment = &mut ctx.accounts.review_commitment;
+    assert_eq!(org.weights.len(), scores.len());
+    assert!(!review_commitment.revealed);
+    let now = Clock::get()?.unix_timestamp;
+    let cycle = review_commitment.cycle;
+    assert!(now >= org.reveal_start(cycle) && now < org.cycle_end(cycle));
+    assert_eq!(
+        ReviewCommitment::hash(ctx.accounts.applicant.key, &scores, relationship, &salt),
+        commitment
+    );
+    review_commitment.revealed = true;
+    ctx.accounts.reveal_receipt.bump = *ctx.bumps.get("reveal_receipt").unwrap();
+
+    // the same limits, weights and bookkeeping as `receive_score`, only the scores
+    // wait in the batch until the cycle is settled
+    ctx.accounts.reviewer_counter.bump = *ctx.bumps.get("reviewer_counter").unwrap();
+    ctx.accounts.pair_counter.bump = *ctx.bumps.get("pair_counter").unwrap();
+    enforce_review_limits(
+        org,
+        &mut ctx.accounts.reviewer_counter,
+        &mut ctx.accounts.pair_counter,
+        now,
+    );
+    // a member can only reveal a self assessment of themselves, which doesn't count as
+    // sent, so no `reviewer_score` next to the member's score
+    let score = &mut ctx.accounts.score;
+    let self_assessment = relationship == Relationship::SelfAssessment;
+    assert_eq!(
+        self_assessment,
+        score.applicant == ctx.accounts.reviewer.key()
+    );
+    let reviewer_levels = if self_assessment {
+        assert!(ctx.accounts.reviewer_score.is_none());
+        score.levels.clone()
+    } else {
+        let reviewer_score = ctx
+            .accounts
+            .reviewer_score
+            .as_mut()
+            .ok_or(GrowthError::ReviewerScoreMissing)?;
+        reviewer_score.record_review_sent(org, now);
+        reviewer_score.levels.clone()
+    };
+    let relationship = score.review_relationship(ctx.accounts.reviewer.key, relationship);
+    let review = SubmittedReview {
+        weights: org.review_weights(Some(&reviewer_levels), relationship),
+        scores,
+        relationship,
+    };
+
+    let batch = &mut ctx.accounts.reveal_batch;
+    batch.ensure_initialized(
+        org,
+        score.key(),
+        cycle,
+        *ctx.bumps.get("reveal_batch").unwrap(),
+    );
+    batch.add(&review.scores, &review.weights, review.relationship);
+
+    let review_record = &mut ctx.accounts.review_record;
+    review_record.set_inner(ReviewRecord::new(
+        score,
+        ctx.accounts.reviewer.key(),
+        &review,
+        now,
+        now,
+        *ctx.bumps.get("review_record").unwrap(),
+    ));
+    review_record.sent_counted = !self_assessment;
+    review_record.reveal_batch = batch.key();
+    score.review_count += 1;
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::GrowthError,
+    events::{ReviewRevoked, ReviewRewardClawedBack},
+    state::{Org, RevealBatch, ReviewRecord, ReviewerRewards, Score, Team, PAUSE_REVIEWS},
+    utils::sync_team,
+};
+
+#[derive(Accounts)]
+#[instruction(index: u32)]
+pub struct RevokeReviewCTX<'info> {
+    pub signer: Signer<'info>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account()]
+    pub applicant: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
+        bump,
+        constraint = score.is_migrated(&org) @ GrowthError::ScoreNotMigrated,
+    )]
+    pub score: Account<'info, Score>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(address = org.parent)]
+    pub parent: Option<Account<'info, Org>>,
+    #[account(
+        mut,
+        seeds = [b"review", score.key().as_ref(), index.to_le_bytes().as_ref()],
+        bump = review_record.bump,
+    )]
+    pub review_record: Account<'info, ReviewRecord>,
+    #[account(
+        mut,
+        seeds = [b"reviewer_rewards", org.key().as_ref(), review_record.reviewer.as_ref()],
+        bump = reviewer_rewards.bump,
+    )]
+    pub reviewer_rewards: Option<Account<'info, ReviewerRewards>>,
+    #[account(
+        mut,
+        seeds = [b"score", org.key().as_ref(), review_record.reviewer.as_ref()],
+        bump,
+    )]
+    pub reviewer_score: Option<Account<'info, Score>>,
+    #[account(constraint = reveal_batch.key() == review_record.reveal_batch)]
+    pub reveal_batch: Option<Account<'info, RevealBatch>>,
+    #[account(mut)]
+    pub team: Option<Account<'info, Team>>,
+}
+
+// The reviewer can take a review back within the org's grace period, the authority
+// and the parent org's authority can at any time.
+pub fn revoke_review(ctx: Context<RevokeReviewCTX>, _index: u32) -> Result<()> {
+    assert!(!ctx.accounts.org.is_paused(PAUSE_REVIEWS));
+    let signer = ctx.accounts.signer.key();
+    if !ctx
+        .accounts
+        .org
+        .can_oversee(&signer, ctx.accounts.parent.as_ref())
+    {
+        assert_eq!(ctx.accounts.review_record.reviewer, signer);
+        assert!(
+            Clock::get()?.unix_timestamp
+                <= ctx.accounts.review_record.recorded_at + ctx.accounts.org.review_grace_period
+        );
+    }
+    ensure_reveal_settled(
+        &ctx.accounts.review_record,
+        ctx.accounts.reveal_batch.as_ref(),
+    )?;
+    apply_revocation(
+        &ctx.accounts.org,
+        &mut ctx.accounts.score,
+        &mut ctx.accounts.review_record,
+        ctx.accounts.reviewer_rewards.as_mut(),
+        ctx.accounts.reviewer_score.as_mut(),
+        ctx.accounts.team.as_mut(),
+        signer,
+    )
+}
+
+// A revealed review only reaches the score when its batch is settled, so it can't be
+// taken out before that.
+pub fn ensure_reveal_settled(
+    review_record: &ReviewRecord,
+    reveal_batch: Option<&Account<'_, RevealBatch>>,
+) -> Result<()> {
+    if review_record.reveal_batch != Pubkey::default() {
+        let reveal_batch = reveal_batch.ok_or(GrowthError::RevealBatchMissing)?;
+        assert!(reveal_batch.settled);
+    }
+    Ok(())
+}
+
+// Levels aren't touched here, `evaluate_level` or the next review applies them. A
+// reward paid for the review is clawed back from the reviewer's future rewards and
+// the review no longer counts as sent by the reviewer.
+pub fn apply_revocation(
+    org: &Account<'_, Org>,
+    score: &mut Account<'_, Score>,
+    review_record: &mut Account<'_, ReviewRecord>,
+    reviewer_rewards: Option<&mut Account<'_, ReviewerRewards>>,
+    reviewer_score: Option<&mut Account<'_, Score>>,
+    team: Option<&mut Account<'_, Team>>,
+    revoked_by: Pubkey,
+) -> Result<()> {
+    assert!(!review_record.revoked);
+    score.remove_review(&review_record.scores, &review_record.weights);
+    score.remove_relationship_review(review_record.relationship, &review_record.scores);
+    let old_scores = score.scores.clone();
+    score.reconcile(org);
+    sync_team(org, score, team, &old_scores, &score.levels)?;
+    review_record.revoked = true;
+    review_record.revoked_by = revoked_by;
+    emit!(ReviewRevoked {
+        org: org.key(),
+        member: score.applicant,
+        reviewer: review_record.reviewer,
+        index: review_record.index,
+        revoked_by,
+    });
+    if review_record.sent_counted {
+        let reviewer_score = reviewer_score.ok_or(GrowthError::ReviewerScoreMissing)?;
+        reviewer_score.remove_review_sent(review_record.recorded_at);
+    }
+    if review_record.reward_paid > 0 {
+        let reviewer_rewards = reviewer_rewards.ok_or(GrowthError::ReviewerRewardsMissing)?;
+        reviewer_rewards.claw_back(review_record.reward_paid);
+        emit!(ReviewRewardClawedBack {
+            org: org.key(),
+            reviewer: review_record.reviewer,
+            amount: review_record.reward_paid,
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Org, OrgRollup, RolledUpScore, Score};
+
+#[derive(Accounts)]
+pub struct RollUpScoreCTX<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account()]
+    pub applicant: AccountInfo<'info>,
+    #[account(
+        seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
+        bump,
+    )]
+    pub score: Account<'info, Score>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(address = org.parent)]
+    pub parent: Account<'info, Org>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        seeds = [b"rollup", parent.key().as_ref(), org.key().as_ref()],
+        bump,
+        space = OrgRollup::space(&parent)
+    )]
+    pub rollup: Account<'info, OrgRollup>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        seeds = [b"rollup_score", parent.key().as_ref(), score.key().as_ref()],
+        bump,
+        space = RolledUpScore::space(&parent)
+    )]
+    pub rolled_up_score: Account<'info, RolledUpScore>,
+    pub system_program: Program<'info, System>,
+}
+
+// Anyone can crank a member's roll-up, it only derives values from the child score.
+pub fn roll_up_score(ctx: Context<RollUpScoreCTX>) -> Result<()> {
+    let org = &ctx.accounts.org;
+    let parent = &ctx.accounts.parent;
+    let score = &ctx.accounts.score;
+    assert!(!score.pending);
+    let rollup = &mut ctx.accounts.rollup;
+    rollup.ensure_initialized(parent, org.key(), *ctx.bumps.get("rollup").unwrap());
+    if rollup.epoch != org.parent_epoch {
+        // the org was re-attached or its range map changed since, start over
+        rollup.reset(parent, org.parent_epoch);
+    }
+
+    let rolled_up = &mut ctx.accounts.rolled_up_score;
+    if rolled_up.scores.is_empty() || rolled_up.epoch != rollup.epoch {
+        rolled_up.parent = parent.key();
+        rolled_up.score = score.key();
+        rolled_up.epoch = rollup.epoch;
+        rolled_up.bump = *ctx.bumps.get("rolled_up_score").unwrap();
+        rollup.aggregate.member_count += 1;
+    } else {
+        rollup
+            .aggregate
+            .remove_contribution(parent, &rolled_up.scores, &rolled_up.levels)?;
+    }
+    let scores = org.roll_up_scores(parent, &score.scores);
+    let levels = parent.levels_for(&scores);
+    msg!("rolled up scores:{:?} levels:{:?}", scores, levels);
+    rollup
+        .aggregate
+        .add_contribution(parent, &scores, &levels)?;
+    rolled_up.scores = scores;
+    rolled_up.levels = levels;
+    rolled_up.last_update = Clock::get()?.unix_timestamp;
+    Ok(())
+}
@@ -1,11 +1,40 @@
 use anchor_lang::prelude::*;
+use anchor_spl::{metadata::MetadataAccount, token::Token};
 
-use super::ScoreCTX;
+use crate::{
+    errors::GrowthError,
+    state::{Org, Score, PAUSE_REVIEWS},
+};
 
-pub fn send_score(ctx: Context<ScoreCTX>) -> Result<()> {
+#[derive(Accounts)]
+pub struct SendScoreCTX<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account()]
+    pub applicant: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
+        bump,
+        constraint = score.is_migrated(&org) @ GrowthError::ScoreNotMigrated,
+    )]
+    pub score: Account<'info, Score>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(mut)]
+    pub metadata: Account<'info, MetadataAccount>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub token_metadata_program: UncheckedAccount<'info>,
+}
+
+pub fn send_score(ctx: Context<SendScoreCTX>) -> Result<()> {
+    assert!(!ctx.accounts.org.is_paused(PAUSE_REVIEWS));
     let score = &mut ctx.accounts.score;
-    let org = &mut ctx.accounts.org;
+    let org = &ctx.accounts.org;
     assert_eq!(org.authority.key(), ctx.accounts.authority.key());
-    score.reviews_sent += 1;
+    score.record_review_sent(org, Clock::get()?.unix_timestamp);
     Ok(())
 }
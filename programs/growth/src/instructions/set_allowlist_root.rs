@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+use super::UpdateOrgCTX;
+
+pub fn set_allowlist_root(ctx: Context<UpdateOrgCTX>, allowlist_root: [u8; 32]) -> Result<()> {
+    assert_eq!(
+        ctx.accounts.org.authority.key(),
+        ctx.accounts.authority.key()
+    );
+    msg!("allowlist root:{:?}", allowlist_root);
+    ctx.accounts.org.allowlist_root = allowlist_root;
+    Ok(())
+}
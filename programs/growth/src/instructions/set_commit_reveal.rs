@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use super::UpdateOrgCTX;
+
+pub fn set_commit_reveal(
+    ctx: Context<UpdateOrgCTX>,
+    commit_reveal: bool,
+    reveal_window: i64,
+) -> Result<()> {
+    assert_eq!(
+        ctx.accounts.org.authority.key(),
+        ctx.accounts.authority.key()
+    );
+    // cycles are `review_period` long, the last `reveal_window` seconds of each are
+    // for reveals
+    if commit_reveal {
+        assert!(reveal_window > 0);
+        assert!(reveal_window < ctx.accounts.org.review_period);
+    }
+    msg!(
+        "commit reveal:{} reveal window:{}",
+        commit_reveal,
+        reveal_window
+    );
+    let org = &mut ctx.accounts.org;
+    org.commit_reveal = commit_reveal;
+    org.reveal_window = reveal_window;
+    Ok(())
+}
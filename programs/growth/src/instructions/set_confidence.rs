@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+use super::UpdateOrgCTX;
+
+pub fn set_confidence(ctx: Context<UpdateOrgCTX>, confidence_z: f32) -> Result<()> {
+    assert_eq!(
+        ctx.accounts.org.authority.key(),
+        ctx.accounts.authority.key()
+    );
+    assert!(confidence_z >= 0.0);
+    msg!("confidence z:{}", confidence_z);
+    ctx.accounts.org.confidence_z = confidence_z;
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use super::UpdateOrgCTX;
+use crate::utils::Realloc;
+
+pub fn set_level_rewards(
+    ctx: Context<UpdateOrgCTX>,
+    reward_mint: Pubkey,
+    level_rewards: Vec<Vec<u64>>,
+) -> Result<()> {
+    assert_eq!(
+        ctx.accounts.org.authority.key(),
+        ctx.accounts.authority.key()
+    );
+    let org = &mut ctx.accounts.org;
+    // one amount per level of every dimension, the amount at index `l` is paid for
+    // reaching level `l + 1`
+    assert_eq!(org.levels.len(), level_rewards.len());
+    for (p1, rewards) in level_rewards.iter().enumerate() {
+        assert_eq!(org.levels[p1].len(), rewards.len());
+    }
+    if org.level_rewards.is_empty() {
+        let space_to_add = level_rewards.iter().map(|r| 4 + 8 * r.len()).sum();
+        org.realloc(
+            space_to_add,
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?;
+    }
+    msg!(
+        "reward mint:{} level rewards:{:?}",
+        reward_mint,
+        level_rewards
+    );
+    org.reward_mint = reward_mint;
+    org.level_rewards = level_rewards;
+    Ok(())
+}
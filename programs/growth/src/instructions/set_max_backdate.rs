@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use super::UpdateOrgCTX;
+
+pub fn set_max_backdate(
+    ctx: Context<UpdateOrgCTX>,
+    max_backdate: i64,
+    import_enabled: bool,
+) -> Result<()> {
+    assert_eq!(
+        ctx.accounts.org.authority.key(),
+        ctx.accounts.authority.key()
+    );
+    assert!(max_backdate >= 0);
+    // an import window of zero can't accept any override, so don't let the
+    // flag claim otherwise
+    assert!(!import_enabled || max_backdate > 0);
+    msg!("max backdate:{} import:{}", max_backdate, import_enabled);
+    ctx.accounts.org.max_backdate = max_backdate;
+    ctx.accounts.org.import_enabled = import_enabled;
+    Ok(())
+}
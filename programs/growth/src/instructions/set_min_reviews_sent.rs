@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use super::UpdateOrgCTX;
+
+pub fn set_min_reviews_sent(
+    ctx: Context<UpdateOrgCTX>,
+    min_reviews_sent: u16,
+    reviews_sent_per_period: bool,
+) -> Result<()> {
+    assert_eq!(
+        ctx.accounts.org.authority.key(),
+        ctx.accounts.authority.key()
+    );
+    msg!(
+        "min reviews sent:{} per period:{}",
+        min_reviews_sent,
+        reviews_sent_per_period
+    );
+    let org = &mut ctx.accounts.org;
+    org.min_reviews_sent = min_reviews_sent;
+    org.reviews_sent_per_period = reviews_sent_per_period;
+    Ok(())
+}
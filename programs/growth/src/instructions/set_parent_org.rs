@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{Org, OrgRollup},
+    utils::Realloc,
+};
+
+#[derive(Accounts)]
+pub struct SetParentOrgCTX<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub parent_authority: Signer<'info>,
+    #[account(mut)]
+    pub org: Account<'info, Org>,
+    #[account()]
+    pub parent: Account<'info, Org>,
+    #[account(
+        mut,
+        seeds = [b"rollup", parent.key().as_ref(), org.key().as_ref()],
+        bump = rollup.bump,
+    )]
+    pub rollup: Option<Account<'info, OrgRollup>>,
+    pub system_program: Program<'info, System>,
+}
+
+// Both authorities sign since the parent gets oversight over the child. An empty
+// map detaches the org from its parent. Either way members rolled up so far no
+// longer count: the new epoch makes `roll_up_score` start the rollup over, and a
+// passed `rollup` is cleared right away since nothing rolls up after a detach.
+pub fn set_parent_org(ctx: Context<SetParentOrgCTX>, parent_range_map: Vec<u8>) -> Result<()> {
+    assert_eq!(
+        ctx.accounts.org.authority.key(),
+        ctx.accounts.authority.key()
+    );
+    assert_eq!(
+        ctx.accounts.parent.authority.key(),
+        ctx.accounts.parent_authority.key()
+    );
+    let parent = &ctx.accounts.parent;
+    let org = &mut ctx.accounts.org;
+    org.parent_epoch += 1;
+    if let Some(rollup) = ctx.accounts.rollup.as_mut() {
+        rollup.reset(parent, org.parent_epoch);
+    }
+    if parent_range_map.is_empty() {
+        assert_eq!(org.parent, parent.key());
+        msg!("detached from parent:{}", parent.key());
+        org.parent = Pubkey::default();
+        org.parent_range_map = vec![];
+        return Ok(());
+    }
+    assert_ne!(parent.key(), org.key());
+    assert_ne!(parent.parent, org.key());
+    assert_eq!(parent_range_map.len(), org.ranges.len() + 1);
+    assert!(parent_range_map
+        .iter()
+        .all(|group| (*group as usize) < parent.ranges.len() + 1));
+    if parent_range_map.len() > org.parent_range_map.len() {
+        let space_to_add = parent_range_map.len() - org.parent_range_map.len();
+        org.realloc(
+            space_to_add,
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?;
+    }
+    msg!("parent:{} range map:{:?}", parent.key(), parent_range_map);
+    org.parent = parent.key();
+    org.parent_range_map = parent_range_map;
+    Ok(())
+}
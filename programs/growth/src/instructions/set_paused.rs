@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Org;
+
+#[derive(Accounts)]
+pub struct SetPausedCTX<'info> {
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub org: Account<'info, Org>,
+    #[account(address = org.parent)]
+    pub parent: Option<Account<'info, Org>>,
+}
+
+pub fn set_paused(ctx: Context<SetPausedCTX>, paused: u8) -> Result<()> {
+    assert!(ctx
+        .accounts
+        .org
+        .can_oversee(ctx.accounts.authority.key, ctx.accounts.parent.as_ref()));
+    msg!("paused:{:#06b}", paused);
+    ctx.accounts.org.paused = paused;
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use super::UpdateOrgCTX;
+
+pub fn set_promotion_approval(
+    ctx: Context<UpdateOrgCTX>,
+    require_promotion_approval: bool,
+    promotion_approver: Pubkey,
+) -> Result<()> {
+    assert_eq!(
+        ctx.accounts.org.authority.key(),
+        ctx.accounts.authority.key()
+    );
+    msg!(
+        "promotion approval:{} approver:{}",
+        require_promotion_approval,
+        promotion_approver
+    );
+    ctx.accounts.org.require_promotion_approval = require_promotion_approval;
+    ctx.accounts.org.promotion_approver = promotion_approver;
+    Ok(())
+}
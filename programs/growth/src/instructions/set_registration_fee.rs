@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use super::UpdateOrgCTX;
+use crate::state::FeePayer;
+
+pub fn set_registration_fee(
+    ctx: Context<UpdateOrgCTX>,
+    registration_fee: u64,
+    fee_mint: Pubkey,
+    fee_payer: FeePayer,
+) -> Result<()> {
+    assert_eq!(
+        ctx.accounts.org.authority.key(),
+        ctx.accounts.authority.key()
+    );
+    msg!(
+        "registration fee:{} mint:{} payer:{:?}",
+        registration_fee,
+        fee_mint,
+        fee_payer
+    );
+    let org = &mut ctx.accounts.org;
+    org.registration_fee = registration_fee;
+    org.fee_mint = fee_mint;
+    org.fee_payer = fee_payer;
+    Ok(())
+}
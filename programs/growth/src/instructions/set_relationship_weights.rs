@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use super::UpdateOrgCTX;
+use crate::state::{MAX_REVIEW_WEIGHT, RELATIONSHIP_TYPES};
+
+pub fn set_relationship_weights(
+    ctx: Context<UpdateOrgCTX>,
+    relationship_weights: [u16; RELATIONSHIP_TYPES],
+    include_self_reviews: bool,
+) -> Result<()> {
+    assert_eq!(
+        ctx.accounts.org.authority.key(),
+        ctx.accounts.authority.key()
+    );
+    assert!(relationship_weights.iter().all(|w| *w <= MAX_REVIEW_WEIGHT));
+    msg!(
+        "relationship weights:{:?} include self reviews:{}",
+        relationship_weights,
+        include_self_reviews
+    );
+    let org = &mut ctx.accounts.org;
+    org.relationship_weights = relationship_weights;
+    org.include_self_reviews = include_self_reviews;
+    Ok(())
+}
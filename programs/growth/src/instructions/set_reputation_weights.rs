@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use super::UpdateOrgCTX;
+use crate::{state::MAX_REVIEW_WEIGHT, utils::Realloc};
+
+pub fn set_reputation_weights(
+    ctx: Context<UpdateOrgCTX>,
+    reputation_weights: Vec<u16>,
+) -> Result<()> {
+    assert_eq!(
+        ctx.accounts.org.authority.key(),
+        ctx.accounts.authority.key()
+    );
+    assert!(reputation_weights
+        .iter()
+        .all(|w| *w > 0 && *w <= MAX_REVIEW_WEIGHT));
+    let org = &mut ctx.accounts.org;
+    if reputation_weights.len() > org.reputation_weights.len() {
+        let space_to_add = 2 * (reputation_weights.len() - org.reputation_weights.len());
+        org.realloc(
+            space_to_add,
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?;
+    }
+    msg!("reputation weights:{:?}", reputation_weights);
+    org.reputation_weights = reputation_weights;
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+use super::UpdateOrgCTX;
+
+pub fn set_review_grace_period(ctx: Context<UpdateOrgCTX>, review_grace_period: i64) -> Result<()> {
+    assert_eq!(
+        ctx.accounts.org.authority.key(),
+        ctx.accounts.authority.key()
+    );
+    assert!(review_grace_period >= 0);
+    msg!("review grace period:{}", review_grace_period);
+    ctx.accounts.org.review_grace_period = review_grace_period;
+    Ok(())
+}
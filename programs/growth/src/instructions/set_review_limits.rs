@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use super::UpdateOrgCTX;
+
+pub fn set_review_limits(
+    ctx: Context<UpdateOrgCTX>,
+    reviewer_cycle: i64,
+    max_reviews_per_cycle: u16,
+    review_period: i64,
+    max_pair_reviews_per_period: u16,
+    min_review_interval: i64,
+) -> Result<()> {
+    assert_eq!(
+        ctx.accounts.org.authority.key(),
+        ctx.accounts.authority.key()
+    );
+    assert!(reviewer_cycle >= 0);
+    assert!(review_period >= 0);
+    assert!(min_review_interval >= 0);
+    // commit-reveal cycles are `review_period` long
+    assert!(!ctx.accounts.org.commit_reveal || review_period > ctx.accounts.org.reveal_window);
+    let org = &mut ctx.accounts.org;
+    org.reviewer_cycle = reviewer_cycle;
+    org.max_reviews_per_cycle = max_reviews_per_cycle;
+    org.review_period = review_period;
+    org.max_pair_reviews_per_period = max_pair_reviews_per_period;
+    org.min_review_interval = min_review_interval;
+    Ok(())
+}
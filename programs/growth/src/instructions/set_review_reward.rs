@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+use super::UpdateOrgCTX;
+
+pub fn set_review_reward(
+    ctx: Context<UpdateOrgCTX>,
+    review_reward: u64,
+    max_review_rewards_per_period: u64,
+) -> Result<()> {
+    assert_eq!(
+        ctx.accounts.org.authority.key(),
+        ctx.accounts.authority.key()
+    );
+    // review rewards are paid from the level rewards vault
+    assert!(review_reward == 0 || ctx.accounts.org.pays_level_rewards());
+    let org = &mut ctx.accounts.org;
+    org.review_reward = review_reward;
+    org.max_review_rewards_per_period = max_review_rewards_per_period;
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Org, ScoringMethod};
+
+#[derive(Accounts)]
+pub struct UpdateOrgCTX<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub org: Account<'info, Org>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_scoring_method(ctx: Context<UpdateOrgCTX>, scoring_method: ScoringMethod) -> Result<()> {
+    assert_eq!(
+        ctx.accounts.org.authority.key(),
+        ctx.accounts.authority.key()
+    );
+    if let ScoringMethod::BayesianAverage {
+        prior_mean,
+        prior_weight,
+    } = scoring_method
+    {
+        // the prior ends up in every score, a NaN can't be serialized
+        assert!(prior_mean.is_finite());
+        assert!(prior_weight.is_finite() && prior_weight >= 0.0);
+    }
+    msg!("scoring method:{:?}", scoring_method);
+    ctx.accounts.org.scoring_method = scoring_method;
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Org, Team};
+
+#[derive(Accounts)]
+pub struct SetTeamLeadCTX<'info> {
+    pub authority: Signer<'info>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(
+        mut,
+        seeds = [b"team", org.key().as_ref(), team.name.as_bytes()],
+        bump = team.bump,
+    )]
+    pub team: Account<'info, Team>,
+}
+
+pub fn set_team_lead(ctx: Context<SetTeamLeadCTX>, lead: Pubkey) -> Result<()> {
+    assert_eq!(
+        ctx.accounts.org.authority.key(),
+        ctx.accounts.authority.key()
+    );
+    msg!("team:{} lead:{}", ctx.accounts.team.name, lead);
+    ctx.accounts.team.lead = lead;
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::GrowthError,
+    state::{Org, RevealBatch, Score, Team, PAUSE_REVIEWS},
+    utils::sync_team,
+};
+
+#[derive(Accounts)]
+#[instruction(cycle: i64)]
+pub struct SettleRevealsCTX<'info> {
+    pub cranker: Signer<'info>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account()]
+    pub applicant: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
+        bump,
+        constraint = score.is_migrated(&org) @ GrowthError::ScoreNotMigrated,
+    )]
+    pub score: Account<'info, Score>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(
+        mut,
+        seeds = [b"batch", score.key().as_ref(), cycle.to_le_bytes().as_ref()],
+        bump = reveal_batch.bump,
+    )]
+    pub reveal_batch: Account<'info, RevealBatch>,
+    #[account(mut)]
+    pub team: Option<Account<'info, Team>>,
+}
+
+// Levels aren't touched here, `evaluate_level` or the next review applies them.
+pub fn settle_reveals(ctx: Context<SettleRevealsCTX>, cycle: i64) -> Result<()> {
+    assert!(!ctx.accounts.org.is_paused(PAUSE_REVIEWS));
+    let batch = &mut ctx.accounts.reveal_batch;
+    assert!(!batch.settled);
+    assert!(Clock::get()?.unix_timestamp >= ctx.accounts.org.cycle_end(cycle));
+    msg!("settling {} revealed reviews", batch.reviews);
+    let old_scores = ctx.accounts.score.scores.clone();
+    ctx.accounts.score.merge_batch(batch);
+    ctx.accounts.score.reconcile(&ctx.accounts.org);
+    sync_team(
+        &ctx.accounts.org,
+        &ctx.accounts.score,
+        ctx.accounts.team.as_mut(),
+        &old_scores,
+        &ctx.accounts.score.levels,
+    )?;
+    batch.settled = true;
+    Ok(())
+}
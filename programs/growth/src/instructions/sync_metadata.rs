@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::MetadataAccount;
+
+use crate::{
+    state::{Org, Score, PAUSE_METADATA},
+    utils::update_score_uri,
+};
+
+#[derive(Accounts)]
+pub struct SyncMetadataCTX<'info> {
+    pub cranker: Signer<'info>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account()]
+    pub applicant: AccountInfo<'info>,
+    #[account(
+        seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
+        bump,
+    )]
+    pub score: Account<'info, Score>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(mut, constraint = metadata.mint == score.mint)]
+    pub metadata: Account<'info, MetadataAccount>,
+    /// CHECK: The org PDA signs the metadata update, so only the real program is accepted
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+}
+
+pub fn sync_metadata(ctx: Context<SyncMetadataCTX>) -> Result<()> {
+    assert!(!ctx.accounts.org.is_paused(PAUSE_METADATA));
+    assert!(!ctx.accounts.score.pending);
+    let org = &ctx.accounts.org;
+    let uri = org.level_uri(&ctx.accounts.score.levels);
+    // metaplex pads the stored uri with zeroes
+    if ctx.accounts.metadata.data.uri.trim_end_matches('\0') == uri {
+        msg!("metadata already in sync");
+        return Ok(());
+    }
+    update_score_uri(
+        org,
+        &ctx.accounts.metadata,
+        &ctx.accounts.token_metadata_program,
+        &ctx.accounts.score.levels,
+    )
+}
@@ -1,17 +1,102 @@
 use anchor_lang::prelude::*;
-use anchor_spl::metadata::{update_metadata_accounts_v2, UpdateMetadataAccountsV2};
-use mpl_token_metadata::state::Collection;
+use anchor_spl::{
+    metadata::MetadataAccount,
+    token::{Token, TokenAccount},
+};
 
-use super::*;
+use crate::{
+    errors::GrowthError,
+    events::ScoresOverridden,
+    state::{
+        LevelChange, LevelChangeSource, Org, OverrideRecord, PendingPromotion, RewardClaims, Score,
+        ScoreHistory, ScoreSnapshot, Team, PAUSE_LEVEL_CHANGES,
+    },
+    utils::{pay_level_rewards, sync_team, update_score_uri, RewardAccounts},
+};
+
+#[derive(Accounts)]
+pub struct UpdateScoresCTX<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    #[account()]
+    pub applicant: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"score", org.key().as_ref(), applicant.key().as_ref()],
+        bump,
+        constraint = score.is_migrated(&org) @ GrowthError::ScoreNotMigrated,
+    )]
+    pub score: Account<'info, Score>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"history", score.key().as_ref()],
+        bump,
+        space = ScoreHistory::space(&org)
+    )]
+    pub history: Account<'info, ScoreHistory>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [
+            b"override",
+            org.key().as_ref(),
+            applicant.key().as_ref(),
+            score.override_count.to_le_bytes().as_ref()
+        ],
+        bump,
+        space = OverrideRecord::space(&org)
+    )]
+    pub override_record: Account<'info, OverrideRecord>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"promotion", score.key().as_ref()],
+        bump,
+        space = PendingPromotion::space(&org)
+    )]
+    pub promotion: Option<Account<'info, PendingPromotion>>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"reward_claims", score.key().as_ref()],
+        bump,
+        space = RewardClaims::space(&org)
+    )]
+    pub reward_claims: Option<Account<'info, RewardClaims>>,
+    #[account(mut, token::mint = org.reward_mint, token::authority = org)]
+    pub reward_vault: Option<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = org.reward_mint,
+        associated_token::authority = applicant
+    )]
+    pub member_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub team: Option<Account<'info, Team>>,
+    #[account(mut, constraint = metadata.mint == score.mint)]
+    pub metadata: Account<'info, MetadataAccount>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    /// CHECK: The org PDA signs the metadata update, so only the real program is accepted
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+}
 
 pub fn update_scores(
-    ctx: Context<ScoreCTX>,
+    ctx: Context<UpdateScoresCTX>,
     scores_sum: Vec<f32>,
     reviews_recieved: Vec<u16>,
     last_update: i64,
     levels: Vec<u8>,
     override_levels: bool,
+    reason_hash: [u8; 32],
 ) -> Result<()> {
+    assert!(!ctx.accounts.org.is_paused(PAUSE_LEVEL_CHANGES));
+    assert!(!ctx.accounts.score.pending);
     assert_eq!(ctx.accounts.org.weights.len(), scores_sum.len());
     assert_eq!(ctx.accounts.org.weights.len(), reviews_recieved.len());
     assert_eq!(ctx.accounts.org.ranges.len() + 1, levels.len());
@@ -19,58 +104,101 @@ pub fn update_scores(
         ctx.accounts.org.authority.key(),
         ctx.accounts.authority.key()
     );
+    assert_ne!(reason_hash, [0; 32]);
+    let clock = Clock::get()?;
+
+    let score_key = ctx.accounts.score.key();
+    ctx.accounts
+        .history
+        .ensure_initialized(score_key, *ctx.bumps.get("history").unwrap());
+    let before = ScoreSnapshot::from(&*ctx.accounts.score);
+    let old_scores = ctx.accounts.score.scores.clone();
 
     ctx.accounts.score.scores_sum = scores_sum.clone();
     ctx.accounts.score.reviews_recieved = reviews_recieved;
+    ctx.accounts.score.reset_review_stats();
 
     let next_level = ctx.accounts.score.reconcile(&ctx.accounts.org);
-    ctx.accounts.score.levels = next_level;
+    let new_levels = if override_levels { levels } else { next_level };
     ctx.accounts.score.last_update = last_update;
 
-    if override_levels {
-        ctx.accounts.score.levels = levels;
+    if new_levels != before.levels {
+        if ctx.accounts.org.require_promotion_approval {
+            // the scores are overwritten right away, the level change waits for the approver
+            ctx.accounts
+                .promotion
+                .as_mut()
+                .ok_or(GrowthError::PromotionAccountMissing)?
+                .request(
+                    score_key,
+                    before.levels.clone(),
+                    new_levels,
+                    clock.unix_timestamp,
+                    ctx.accounts.authority.key(),
+                    *ctx.bumps.get("promotion").unwrap(),
+                );
+        } else {
+            ctx.accounts.history.record(LevelChange {
+                timestamp: clock.unix_timestamp,
+                old_levels: before.levels.clone(),
+                new_levels: new_levels.clone(),
+                source: LevelChangeSource::UpdateScores,
+                signer: ctx.accounts.authority.key(),
+            });
+            ctx.accounts.score.levels = new_levels;
+        }
     }
-    let mut level_string: String = ctx
-        .accounts
-        .score
-        .levels
-        .iter()
-        .map(|&id| id.to_string() + "-")
-        .collect();
-    level_string.pop();
-    let mut uri = ctx.accounts.org.domain.clone();
-    uri.push_str("/");
-    uri.push_str(&level_string);
-    uri.push_str(".json");
 
-    msg!("Updating NFT");
-    let org_mint = ctx.accounts.org.mint.key();
-    let signer: &[&[&[u8]]] = &[&[
-        b"org",
-        org_mint.as_ref(),
-        ctx.accounts.authority.key.as_ref(),
-        &[ctx.accounts.org.bump],
-    ]];
-    let data_v2 = mpl_token_metadata::state::DataV2 {
-        name: ctx.accounts.metadata.data.name.to_string(),
-        symbol: "SCORE".to_string(),
-        uri,
-        seller_fee_basis_points: 0,
-        creators: None,
-        collection: Some(Collection {
-            verified: true,
-            key: ctx.accounts.org.mint.key(),
-        }),
-        uses: None,
-    };
-    let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_metadata_program.to_account_info(),
-        UpdateMetadataAccountsV2 {
-            metadata: ctx.accounts.metadata.to_account_info(),
-            update_authority: ctx.accounts.org.to_account_info(),
-        },
-        signer,
-    );
-    update_metadata_accounts_v2(cpi_ctx, None, Some(data_v2), Some(true), Some(true))?;
-    Ok(())
+    let sequence = ctx.accounts.score.override_count;
+    ctx.accounts.score.override_count += 1;
+    let after = ScoreSnapshot::from(&*ctx.accounts.score);
+    ctx.accounts.override_record.set_inner(OverrideRecord {
+        org: ctx.accounts.org.key(),
+        member: ctx.accounts.applicant.key(),
+        sequence,
+        signer: ctx.accounts.authority.key(),
+        reason_hash,
+        timestamp: clock.unix_timestamp,
+        override_levels,
+        before: before.clone(),
+        after: after.clone(),
+        bump: *ctx.bumps.get("override_record").unwrap(),
+    });
+    emit!(ScoresOverridden {
+        org: ctx.accounts.org.key(),
+        member: ctx.accounts.applicant.key(),
+        sequence,
+        signer: ctx.accounts.authority.key(),
+        reason_hash,
+        timestamp: clock.unix_timestamp,
+        before: before.clone(),
+        after,
+    });
+    sync_team(
+        &ctx.accounts.org,
+        &ctx.accounts.score,
+        ctx.accounts.team.as_mut(),
+        &old_scores,
+        &before.levels,
+    )?;
+
+    update_score_uri(
+        &ctx.accounts.org,
+        &ctx.accounts.metadata,
+        &ctx.accounts.token_metadata_program,
+        &ctx.accounts.score.levels,
+    )?;
+    let token_program = ctx.accounts.token_program.to_account_info();
+    pay_level_rewards(
+        &ctx.accounts.org,
+        &ctx.accounts.score,
+        RewardAccounts::from_optional(
+            ctx.accounts.reward_claims.as_mut(),
+            ctx.bumps.get("reward_claims").copied(),
+            ctx.accounts.reward_vault.as_ref(),
+            ctx.accounts.member_token_account.as_ref(),
+            token_program,
+        ),
+        &before.levels,
+    )
 }
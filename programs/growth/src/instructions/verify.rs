@@ -7,7 +7,7 @@ use anchor_spl::{
     token::Mint,
 };
 
-use crate::state::Org;
+use crate::state::{Org, PAUSE_METADATA};
 
 #[derive(Accounts)]
 pub struct VerifyCTX<'info> {
@@ -29,11 +29,12 @@ pub struct VerifyCTX<'info> {
 }
 
 pub fn verify(ctx: Context<VerifyCTX>) -> Result<()> {
+    assert!(!ctx.accounts.org.is_paused(PAUSE_METADATA));
     assert_eq!(
         ctx.accounts.org.authority.key(),
         ctx.accounts.authority.key()
     );
-    if ctx.accounts.metadata.collection.as_ref().unwrap().verified == true {
+    if ctx.accounts.metadata.collection.as_ref().unwrap().verified {
         return Ok(());
     }
     let mint = ctx.accounts.org.mint.key();
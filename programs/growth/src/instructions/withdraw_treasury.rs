@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+use crate::{
+    errors::GrowthError,
+    events::TreasuryWithdrawn,
+    state::{Org, Treasury},
+};
+
+#[derive(Accounts)]
+pub struct WithdrawTreasuryCTX<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account()]
+    pub org: Account<'info, Org>,
+    #[account(
+        mut,
+        seeds = [b"treasury", org.key().as_ref()],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+    /// CHECK: Only receives lamports when withdrawing SOL
+    #[account(mut)]
+    pub destination: AccountInfo<'info>,
+    #[account(mut, token::authority = treasury)]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub destination_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+}
+
+// Withdraws SPL tokens when the treasury token account is passed, SOL otherwise.
+pub fn withdraw_treasury(ctx: Context<WithdrawTreasuryCTX>, amount: u64) -> Result<()> {
+    assert_eq!(
+        ctx.accounts.org.authority.key(),
+        ctx.accounts.authority.key()
+    );
+    let org_key = ctx.accounts.org.key();
+    let (mint, destination) = match &ctx.accounts.treasury_token_account {
+        Some(treasury_token_account) => {
+            let destination_token_account = ctx
+                .accounts
+                .destination_token_account
+                .as_ref()
+                .ok_or(GrowthError::DestinationTokenAccountMissing)?;
+            let signer: &[&[&[u8]]] =
+                &[&[b"treasury", org_key.as_ref(), &[ctx.accounts.treasury.bump]]];
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: treasury_token_account.to_account_info(),
+                        to: destination_token_account.to_account_info(),
+                        authority: ctx.accounts.treasury.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount,
+            )?;
+            (treasury_token_account.mint, destination_token_account.key())
+        }
+        None => {
+            // the treasury is owned by the program, so lamports are moved directly
+            // while keeping it rent exempt
+            let treasury = ctx.accounts.treasury.to_account_info();
+            let rent = Rent::get()?.minimum_balance(treasury.data_len());
+            assert!(treasury.lamports() >= rent + amount);
+            **treasury.try_borrow_mut_lamports()? -= amount;
+            **ctx.accounts.destination.try_borrow_mut_lamports()? += amount;
+            (Pubkey::default(), ctx.accounts.destination.key())
+        }
+    };
+    emit!(TreasuryWithdrawn {
+        org: org_key,
+        authority: ctx.accounts.authority.key(),
+        mint,
+        destination,
+        amount,
+    });
+    Ok(())
+}
@@ -1,10 +1,17 @@
+// anchor's `Error` is what every instruction returns, and instruction arguments
+// (also in the handlers `#[program]` generates) can't be grouped
+#![allow(clippy::result_large_err, clippy::too_many_arguments)]
+
 use anchor_lang::prelude::*;
 
+mod errors;
+mod events;
 mod instructions;
 mod state;
 mod utils;
 
 use instructions::*;
+use state::{FeePayer, Relationship, ScoringMethod, RELATIONSHIP_TYPES};
 
 declare_id!("97d8t22JenPAwR85PZEXvc4gkvtAMZR9Ct7xuY11a2X8");
 
@@ -43,25 +50,31 @@ pub mod growth {
         instructions::register(ctx, name, levels, last_update)
     }
 
-    pub fn receive_score(ctx: Context<ScoreCTX>, scores: Vec<f32>, submission_ts: i64) -> Result<()> {
-        instructions::receive_score(ctx, scores, submission_ts)
+    pub fn receive_score(
+        ctx: Context<ScoreCTX>,
+        scores: Vec<f32>,
+        submission_ts: i64,
+        relationship: Relationship,
+    ) -> Result<()> {
+        instructions::receive_score(ctx, scores, submission_ts, relationship)
     }
 
     pub fn verify(ctx: Context<VerifyCTX>) -> Result<()> {
         instructions::verify(ctx)
     }
 
-    pub fn send_score(ctx: Context<ScoreCTX>) -> Result<()> {
+    pub fn send_score(ctx: Context<SendScoreCTX>) -> Result<()> {
         instructions::send_score(ctx)
     }
 
     pub fn update_scores(
-        ctx: Context<ScoreCTX>,
+        ctx: Context<UpdateScoresCTX>,
         scores_sum: Vec<f32>,
         reviews_recieved: Vec<u16>,
         last_update: i64,
         levels: Vec<u8>,
         override_levels: bool,
+        reason_hash: [u8; 32],
     ) -> Result<()> {
         instructions::update_scores(
             ctx,
@@ -70,6 +83,268 @@ pub mod growth {
             last_update,
             levels,
             override_levels,
+            reason_hash,
+        )
+    }
+
+    pub fn evaluate_level(ctx: Context<EvaluateLevelCTX>) -> Result<()> {
+        instructions::evaluate_level(ctx)
+    }
+
+    pub fn preview_level(ctx: Context<PreviewLevelCTX>) -> Result<LevelPreview> {
+        instructions::preview_level(ctx)
+    }
+
+    pub fn set_scoring_method(
+        ctx: Context<UpdateOrgCTX>,
+        scoring_method: ScoringMethod,
+    ) -> Result<()> {
+        instructions::set_scoring_method(ctx, scoring_method)
+    }
+
+    pub fn set_confidence(ctx: Context<UpdateOrgCTX>, confidence_z: f32) -> Result<()> {
+        instructions::set_confidence(ctx, confidence_z)
+    }
+
+    pub fn set_promotion_approval(
+        ctx: Context<UpdateOrgCTX>,
+        require_promotion_approval: bool,
+        promotion_approver: Pubkey,
+    ) -> Result<()> {
+        instructions::set_promotion_approval(ctx, require_promotion_approval, promotion_approver)
+    }
+
+    pub fn approve_promotion(ctx: Context<ApprovePromotionCTX>) -> Result<()> {
+        instructions::approve_promotion(ctx)
+    }
+
+    pub fn reject_promotion(ctx: Context<RejectPromotionCTX>, reason_hash: [u8; 32]) -> Result<()> {
+        instructions::reject_promotion(ctx, reason_hash)
+    }
+
+    pub fn set_max_backdate(
+        ctx: Context<UpdateOrgCTX>,
+        max_backdate: i64,
+        import_enabled: bool,
+    ) -> Result<()> {
+        instructions::set_max_backdate(ctx, max_backdate, import_enabled)
+    }
+
+    pub fn apply(ctx: Context<ApplyCTX>, name: String) -> Result<()> {
+        instructions::apply(ctx, name)
+    }
+
+    pub fn approve_application(
+        ctx: Context<ApproveApplicationCTX>,
+        levels: Vec<u8>,
+        last_update: i64,
+    ) -> Result<()> {
+        instructions::approve_application(ctx, levels, last_update)
+    }
+
+    pub fn set_allowlist_root(ctx: Context<UpdateOrgCTX>, allowlist_root: [u8; 32]) -> Result<()> {
+        instructions::set_allowlist_root(ctx, allowlist_root)
+    }
+
+    pub fn register_with_proof(
+        ctx: Context<RegisterWithProofCTX>,
+        name: String,
+        levels: Vec<u8>,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::register_with_proof(ctx, name, levels, proof)
+    }
+
+    pub fn set_paused(ctx: Context<SetPausedCTX>, paused: u8) -> Result<()> {
+        instructions::set_paused(ctx, paused)
+    }
+
+    pub fn receive_signed_score(
+        ctx: Context<SignedScoreCTX>,
+        scores: Vec<f32>,
+        nonce: u64,
+        relationship: Relationship,
+    ) -> Result<()> {
+        instructions::receive_signed_score(ctx, scores, nonce, relationship)
+    }
+
+    pub fn set_review_limits(
+        ctx: Context<UpdateOrgCTX>,
+        reviewer_cycle: i64,
+        max_reviews_per_cycle: u16,
+        review_period: i64,
+        max_pair_reviews_per_period: u16,
+        min_review_interval: i64,
+    ) -> Result<()> {
+        instructions::set_review_limits(
+            ctx,
+            reviewer_cycle,
+            max_reviews_per_cycle,
+            review_period,
+            max_pair_reviews_per_period,
+            min_review_interval,
         )
     }
+
+    pub fn set_registration_fee(
+        ctx: Context<UpdateOrgCTX>,
+        registration_fee: u64,
+        fee_mint: Pubkey,
+        fee_payer: FeePayer,
+    ) -> Result<()> {
+        instructions::set_registration_fee(ctx, registration_fee, fee_mint, fee_payer)
+    }
+
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasuryCTX>, amount: u64) -> Result<()> {
+        instructions::withdraw_treasury(ctx, amount)
+    }
+
+    pub fn set_level_rewards(
+        ctx: Context<UpdateOrgCTX>,
+        reward_mint: Pubkey,
+        level_rewards: Vec<Vec<u64>>,
+    ) -> Result<()> {
+        instructions::set_level_rewards(ctx, reward_mint, level_rewards)
+    }
+
+    pub fn set_review_reward(
+        ctx: Context<UpdateOrgCTX>,
+        review_reward: u64,
+        max_review_rewards_per_period: u64,
+    ) -> Result<()> {
+        instructions::set_review_reward(ctx, review_reward, max_review_rewards_per_period)
+    }
+
+    pub fn set_min_reviews_sent(
+        ctx: Context<UpdateOrgCTX>,
+        min_reviews_sent: u16,
+        reviews_sent_per_period: bool,
+    ) -> Result<()> {
+        instructions::set_min_reviews_sent(ctx, min_reviews_sent, reviews_sent_per_period)
+    }
+
+    pub fn set_review_grace_period(
+        ctx: Context<UpdateOrgCTX>,
+        review_grace_period: i64,
+    ) -> Result<()> {
+        instructions::set_review_grace_period(ctx, review_grace_period)
+    }
+
+    pub fn revoke_review(ctx: Context<RevokeReviewCTX>, index: u32) -> Result<()> {
+        instructions::revoke_review(ctx, index)
+    }
+
+    pub fn open_dispute(
+        ctx: Context<OpenDisputeCTX>,
+        index: u32,
+        reason_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::open_dispute(ctx, index, reason_hash)
+    }
+
+    pub fn resolve_dispute(
+        ctx: Context<ResolveDisputeCTX>,
+        index: u32,
+        uphold: bool,
+        resolution_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::resolve_dispute(ctx, index, uphold, resolution_hash)
+    }
+
+    pub fn set_commit_reveal(
+        ctx: Context<UpdateOrgCTX>,
+        commit_reveal: bool,
+        reveal_window: i64,
+    ) -> Result<()> {
+        instructions::set_commit_reveal(ctx, commit_reveal, reveal_window)
+    }
+
+    pub fn commit_review(ctx: Context<CommitReviewCTX>, commitment: [u8; 32]) -> Result<()> {
+        instructions::commit_review(ctx, commitment)
+    }
+
+    pub fn reveal_review(
+        ctx: Context<RevealReviewCTX>,
+        commitment: [u8; 32],
+        scores: Vec<f32>,
+        relationship: Relationship,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        instructions::reveal_review(ctx, commitment, scores, relationship, salt)
+    }
+
+    pub fn settle_reveals(ctx: Context<SettleRevealsCTX>, cycle: i64) -> Result<()> {
+        instructions::settle_reveals(ctx, cycle)
+    }
+
+    pub fn set_reputation_weights(
+        ctx: Context<UpdateOrgCTX>,
+        reputation_weights: Vec<u16>,
+    ) -> Result<()> {
+        instructions::set_reputation_weights(ctx, reputation_weights)
+    }
+
+    pub fn set_relationship_weights(
+        ctx: Context<UpdateOrgCTX>,
+        relationship_weights: [u16; RELATIONSHIP_TYPES],
+        include_self_reviews: bool,
+    ) -> Result<()> {
+        instructions::set_relationship_weights(ctx, relationship_weights, include_self_reviews)
+    }
+
+    pub fn reassign_manager(ctx: Context<ReassignManagerCTX>, manager: Pubkey) -> Result<()> {
+        instructions::reassign_manager(ctx, manager)
+    }
+
+    pub fn create_team(ctx: Context<CreateTeamCTX>, name: String, lead: Pubkey) -> Result<()> {
+        instructions::create_team(ctx, name, lead)
+    }
+
+    pub fn set_team_lead(ctx: Context<SetTeamLeadCTX>, lead: Pubkey) -> Result<()> {
+        instructions::set_team_lead(ctx, lead)
+    }
+
+    pub fn add_team_member(ctx: Context<TeamMemberCTX>) -> Result<()> {
+        instructions::add_team_member(ctx)
+    }
+
+    pub fn remove_team_member(ctx: Context<TeamMemberCTX>) -> Result<()> {
+        instructions::remove_team_member(ctx)
+    }
+
+    pub fn set_parent_org(ctx: Context<SetParentOrgCTX>, parent_range_map: Vec<u8>) -> Result<()> {
+        instructions::set_parent_org(ctx, parent_range_map)
+    }
+
+    pub fn roll_up_score(ctx: Context<RollUpScoreCTX>) -> Result<()> {
+        instructions::roll_up_score(ctx)
+    }
+
+    pub fn assert_level(
+        ctx: Context<LevelCheckCTX>,
+        range_group: u8,
+        min_level: u8,
+    ) -> Result<()> {
+        instructions::assert_level(ctx, range_group, min_level)
+    }
+
+    pub fn get_levels(ctx: Context<LevelCheckCTX>) -> Result<Vec<u8>> {
+        instructions::get_levels(ctx)
+    }
+
+    pub fn sync_metadata(ctx: Context<SyncMetadataCTX>) -> Result<()> {
+        instructions::sync_metadata(ctx)
+    }
+
+    pub fn claim_level_rewards(ctx: Context<ClaimLevelRewardsCTX>) -> Result<()> {
+        instructions::claim_level_rewards(ctx)
+    }
+
+    pub fn migrate_score(ctx: Context<MigrateScoreCTX>) -> Result<()> {
+        instructions::migrate_score(ctx)
+    }
+
+    pub fn claim_review_reward(ctx: Context<ClaimReviewRewardCTX>, index: u32) -> Result<()> {
+        instructions::claim_review_reward(ctx, index)
+    }
 }
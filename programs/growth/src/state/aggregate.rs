@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+
+use super::Org;
+use crate::errors::GrowthError;
+
+// Member scores summed per range group and members counted per level, dimension
+// after dimension (see `level_index`). Kept up to date by adding and removing each
+// member's contribution as their score changes. The f32 sums pick up rounding from
+// every add and remove, so they drift slightly from the members' actual scores over
+// time. They're reset once the last member leaves.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MemberAggregate {
+    pub member_count: u32,
+    pub scores_sum: Vec<f32>,
+    pub level_counts: Vec<u16>,
+}
+
+impl MemberAggregate {
+    pub fn space(org: &Org) -> usize {
+        4 + (4 + 4 * (org.ranges.len() + 1)) + (4 + 2 * Self::level_slots(org))
+    }
+
+    pub fn new(org: &Org) -> Self {
+        Self {
+            member_count: 0,
+            scores_sum: vec![0 as f32; org.ranges.len() + 1],
+            level_counts: vec![0; Self::level_slots(org)],
+        }
+    }
+
+    // every dimension can be at level 0 up to the number of thresholds
+    fn level_slots(org: &Org) -> usize {
+        org.levels.iter().map(|l| l.len() + 1).sum()
+    }
+
+    // Levels come from member and rolled up accounts, so they're checked against the
+    // org's thresholds instead of indexing blindly.
+    fn level_index(org: &Org, dimension: usize, level: u8) -> Result<usize> {
+        let thresholds = org
+            .levels
+            .get(dimension)
+            .ok_or(GrowthError::LevelOutOfRange)?;
+        require!(
+            level as usize <= thresholds.len(),
+            GrowthError::LevelOutOfRange
+        );
+        let offset: usize = org.levels[..dimension].iter().map(|l| l.len() + 1).sum();
+        Ok(offset + level as usize)
+    }
+
+    pub fn add_member(&mut self, org: &Org, scores: &[f32], levels: &[u8]) -> Result<()> {
+        self.member_count += 1;
+        self.add_contribution(org, scores, levels)
+    }
+
+    pub fn remove_member(&mut self, org: &Org, scores: &[f32], levels: &[u8]) -> Result<()> {
+        self.member_count -= 1;
+        self.remove_contribution(org, scores, levels)?;
+        if self.member_count == 0 {
+            self.scores_sum = vec![0 as f32; self.scores_sum.len()];
+        }
+        Ok(())
+    }
+
+    pub fn average_scores(&self) -> Vec<f32> {
+        if self.member_count == 0 {
+            return vec![0 as f32; self.scores_sum.len()];
+        }
+        self.scores_sum
+            .iter()
+            .map(|sum| sum / self.member_count as f32)
+            .collect()
+    }
+
+    pub fn add_contribution(&mut self, org: &Org, scores: &[f32], levels: &[u8]) -> Result<()> {
+        require!(
+            levels.len() == org.levels.len(),
+            GrowthError::LevelOutOfRange
+        );
+        for (r_index, score) in scores.iter().enumerate() {
+            self.scores_sum[r_index] += score;
+        }
+        for (dimension, level) in levels.iter().enumerate() {
+            self.level_counts[Self::level_index(org, dimension, *level)?] += 1;
+        }
+        Ok(())
+    }
+
+    pub fn remove_contribution(&mut self, org: &Org, scores: &[f32], levels: &[u8]) -> Result<()> {
+        require!(
+            levels.len() == org.levels.len(),
+            GrowthError::LevelOutOfRange
+        );
+        for (r_index, score) in scores.iter().enumerate() {
+            self.scores_sum[r_index] -= score;
+        }
+        for (dimension, level) in levels.iter().enumerate() {
+            let index = Self::level_index(org, dimension, *level)?;
+            self.level_counts[index] = self.level_counts[index].saturating_sub(1);
+        }
+        Ok(())
+    }
+}
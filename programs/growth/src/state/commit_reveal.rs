@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+use super::{Org, Relationship, RELATIONSHIP_TYPES};
+
+#[account]
+pub struct ReviewCommitment {
+    pub org: Pubkey,
+    pub reviewer: Pubkey,
+    pub cycle: i64,
+    pub commitment: [u8; 32],
+    pub revealed: bool,
+    pub bump: u8,
+}
+
+impl ReviewCommitment {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 32 + 1 + 1;
+
+    pub fn hash(
+        reviewee: &Pubkey,
+        scores: &[f32],
+        relationship: Relationship,
+        salt: &[u8; 32],
+    ) -> [u8; 32] {
+        let scores: Vec<u8> = scores.iter().flat_map(|s| s.to_le_bytes()).collect();
+        keccak::hashv(&[reviewee.as_ref(), &scores, &[relationship as u8], salt]).0
+    }
+}
+
+// Only exists so a reviewer can't reveal two reviews of the same member in one cycle.
+#[account]
+pub struct RevealReceipt {
+    pub bump: u8,
+}
+
+impl RevealReceipt {
+    pub const SPACE: usize = 8 + 1;
+}
+
+// Revealed reviews of a member, weighted like `Score::record_review` and merged into
+// the score in one go once the cycle is over. The relationship totals wait here too.
+#[account]
+pub struct RevealBatch {
+    pub score: Pubkey,
+    pub cycle: i64,
+    pub scores_sum: Vec<f32>,
+    pub scores_min: Vec<f32>,
+    pub scores_max: Vec<f32>,
+    pub scores_sq_sum: Vec<f32>,
+    pub scores_min_weight: Vec<u16>,
+    pub scores_max_weight: Vec<u16>,
+    pub reviews_recieved: Vec<u16>,
+    pub weighted_reviews: Vec<u32>,
+    pub relationship_sums: Vec<f32>,
+    pub relationship_counts: Vec<u16>,
+    pub reviews: u16,
+    pub settled: bool,
+    pub bump: u8,
+}
+
+impl RevealBatch {
+    pub fn space(org: &Org) -> usize {
+        8 + 32 + 8
+        + (4 + 4 * org.weights.len()) * 5 // review stats
+        + (4 + 2 * org.weights.len()) * 3 // scores_min_weight, scores_max_weight, reviews_recieved
+        + (8 + 6 * RELATIONSHIP_TYPES * org.weights.len()) // relationship totals
+        + 2 + 1 + 1
+    }
+
+    pub fn ensure_initialized(&mut self, org: &Org, score: Pubkey, cycle: i64, bump: u8) {
+        if self.score == Pubkey::default() {
+            self.score = score;
+            self.cycle = cycle;
+            self.scores_sum = vec![0.0; org.weights.len()];
+            self.scores_min = vec![0.0; org.weights.len()];
+            self.scores_max = vec![0.0; org.weights.len()];
+            self.scores_sq_sum = vec![0.0; org.weights.len()];
+            self.scores_min_weight = vec![0; org.weights.len()];
+            self.scores_max_weight = vec![0; org.weights.len()];
+            self.reviews_recieved = vec![0; org.weights.len()];
+            self.weighted_reviews = vec![0; org.weights.len()];
+            self.relationship_sums = vec![0.0; RELATIONSHIP_TYPES * org.weights.len()];
+            self.relationship_counts = vec![0; RELATIONSHIP_TYPES * org.weights.len()];
+            self.bump = bump;
+        }
+    }
+
+    pub fn add(&mut self, scores: &[f32], weights: &[u16], relationship: Relationship) {
+        let offset = relationship as usize * scores.len();
+        for (p1, e1) in scores.iter().enumerate() {
+            if *e1 != 0 as f32 {
+                self.relationship_sums[offset + p1] += e1;
+                self.relationship_counts[offset + p1] += 1;
+            }
+            let weight = weights[p1] as f32;
+            self.scores_sum[p1] += e1 * weight;
+            if *e1 != 0 as f32 && weights[p1] != 0 {
+                if self.reviews_recieved[p1] == 0 || *e1 < self.scores_min[p1] {
+                    self.scores_min[p1] = *e1;
+                    self.scores_min_weight[p1] = weights[p1];
+                }
+                if self.reviews_recieved[p1] == 0 || *e1 > self.scores_max[p1] {
+                    self.scores_max[p1] = *e1;
+                    self.scores_max_weight[p1] = weights[p1];
+                }
+                self.scores_sq_sum[p1] += e1 * e1 * weight;
+                self.reviews_recieved[p1] += 1;
+                self.weighted_reviews[p1] += weights[p1] as u32;
+            }
+        }
+        self.reviews += 1;
+    }
+}
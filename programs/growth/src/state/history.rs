@@ -0,0 +1,99 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::utils::Realloc;
+
+use super::Org;
+
+pub const HISTORY_CAPACITY: usize = 16;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum LevelChangeSource {
+    ReceiveScore,
+    UpdateScores,
+    EvaluateLevel,
+    ApprovePromotion,
+    SignedReview,
+    ReassignManager {
+        old_manager: Pubkey,
+        new_manager: Pubkey,
+    },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LevelChange {
+    pub timestamp: i64,
+    pub old_levels: Vec<u8>,
+    pub new_levels: Vec<u8>,
+    pub source: LevelChangeSource,
+    pub signer: Pubkey,
+}
+
+#[account]
+pub struct ScoreHistory {
+    pub score: Pubkey,
+    pub next: u16,
+    pub entries: Vec<LevelChange>,
+    pub bump: u8,
+}
+
+impl ScoreHistory {
+    pub fn space(org: &Org) -> usize {
+        // the largest source variant is ReassignManager with two pubkeys
+        let entry = 8 + (4 + org.levels.len()) * 2 + (1 + 64) + 32;
+        8 + 32 + 2 + 4 + HISTORY_CAPACITY * entry + 1
+    }
+
+    pub fn ensure_initialized(&mut self, score: Pubkey, bump: u8) {
+        if self.score == Pubkey::default() {
+            self.score = score;
+            self.bump = bump;
+        }
+    }
+
+    pub fn record(&mut self, entry: LevelChange) {
+        msg!(
+            "level change {:?}: {:?} -> {:?}",
+            entry.source,
+            entry.old_levels,
+            entry.new_levels
+        );
+        if self.entries.len() < HISTORY_CAPACITY {
+            self.entries.push(entry);
+        } else {
+            self.entries[self.next as usize] = entry;
+        }
+        self.next = ((self.next as usize + 1) % HISTORY_CAPACITY) as u16;
+    }
+}
+
+impl<'info> Realloc<'info> for Account<'info, ScoreHistory> {
+    fn realloc(
+        &mut self,
+        space_to_add: usize,
+        payer: &Signer<'info>,
+        system_program: &Program<'info, System>,
+    ) -> Result<()> {
+        let account_info = self.to_account_info();
+        let new_account_size = account_info.data_len() + space_to_add;
+
+        // Determine additional rent required
+        let lamports_required = (Rent::get()?).minimum_balance(new_account_size);
+        let additional_rent_to_fund = lamports_required - account_info.lamports();
+
+        // Perform transfer of additional rent
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account_info.clone(),
+                },
+            ),
+            additional_rent_to_fund,
+        )?;
+
+        // Reallocate the account
+        account_info.realloc(new_account_size, false)?;
+        Ok(())
+    }
+}
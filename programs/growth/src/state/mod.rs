@@ -1,5 +1,31 @@
+mod aggregate;
+mod commit_reveal;
+mod history;
 mod org;
+mod override_record;
+mod promotion;
+mod rate_limit;
+mod review;
+mod reviewer_rewards;
+mod rewards;
+mod rollup;
 mod score;
+mod scoring;
+mod team;
+mod treasury;
 
+pub use aggregate::*;
+pub use commit_reveal::*;
+pub use history::*;
 pub use org::*;
+pub use override_record::*;
+pub use promotion::*;
+pub use rate_limit::*;
+pub use review::*;
+pub use reviewer_rewards::*;
+pub use rewards::*;
+pub use rollup::*;
 pub use score::*;
+pub use scoring::*;
+pub use team::*;
+pub use treasury::*;
@@ -1,7 +1,19 @@
+use std::ops::Range;
+
 use anchor_lang::{prelude::*, system_program};
 
 use crate::utils::Realloc;
 
+use super::{FeePayer, Relationship, ScoringMethod, RELATIONSHIP_TYPES};
+
+pub const PAUSE_REGISTRATIONS: u8 = 1 << 0;
+pub const PAUSE_REVIEWS: u8 = 1 << 1;
+pub const PAUSE_LEVEL_CHANGES: u8 = 1 << 2;
+pub const PAUSE_METADATA: u8 = 1 << 3;
+
+// Cap on every reputation and relationship weight, their product has to fit a u16.
+pub const MAX_REVIEW_WEIGHT: u16 = 100;
+
 #[account]
 // #[derive(Default)]
 pub struct Org {
@@ -14,7 +26,161 @@ pub struct Org {
     pub authority: Pubkey,
     pub domain: String,
     pub bump: u8,
-    pub level_wait: i32
+    pub level_wait: i32,
+    pub scoring_method: ScoringMethod,
+    pub confidence_z: f32,
+    pub require_promotion_approval: bool,
+    pub promotion_approver: Pubkey,
+    pub max_backdate: i64,
+    pub import_enabled: bool,
+    pub allowlist_root: [u8; 32],
+    pub paused: u8,
+    pub review_period: i64,
+    pub reviewer_cycle: i64,
+    pub max_reviews_per_cycle: u16,
+    pub max_pair_reviews_per_period: u16,
+    pub min_review_interval: i64,
+    pub registration_fee: u64,
+    pub fee_mint: Pubkey,
+    pub fee_payer: FeePayer,
+    pub reward_mint: Pubkey,
+    pub level_rewards: Vec<Vec<u64>>,
+    pub review_reward: u64,
+    pub max_review_rewards_per_period: u64,
+    pub min_reviews_sent: u16,
+    pub reviews_sent_per_period: bool,
+    pub review_grace_period: i64,
+    pub commit_reveal: bool,
+    pub reveal_window: i64,
+    pub reputation_weights: Vec<u16>,
+    pub relationship_weights: [u16; RELATIONSHIP_TYPES],
+    pub include_self_reviews: bool,
+    pub parent: Pubkey,
+    pub parent_range_map: Vec<u8>,
+    // bumped on every `set_parent_org`, rollups of an older epoch are stale
+    pub parent_epoch: u32,
+}
+
+impl Org {
+    pub fn level_uri(&self, levels: &[u8]) -> String {
+        let mut level_string: String = levels.iter().map(|&id| id.to_string() + "-").collect();
+        level_string.pop();
+        let mut uri = self.domain.clone();
+        uri.push('/');
+        uri.push_str(&level_string);
+        uri.push_str(".json");
+        uri
+    }
+
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.paused & flag != 0
+    }
+
+    pub fn can_approve_promotions(&self, signer: &Pubkey, parent: Option<&Account<Org>>) -> bool {
+        self.can_oversee(signer, parent) || *signer == self.promotion_approver
+    }
+
+    // The parent org's authority can step in for the authority on pausing,
+    // promotions and review moderation. Its current authority is read from the
+    // parent account, so that has to be passed.
+    pub fn can_oversee(&self, signer: &Pubkey, parent: Option<&Account<Org>>) -> bool {
+        *signer == self.authority
+            || parent.is_some_and(|parent| {
+                self.parent != Pubkey::default()
+                    && parent.key() == self.parent
+                    && *signer == parent.authority
+            })
+    }
+
+    // Averages the scores of the range groups mapped to each of the parent's range
+    // groups. Parent groups nothing maps to stay at 0.
+    pub fn roll_up_scores(&self, parent: &Org, scores: &[f32]) -> Vec<f32> {
+        let mut sums = vec![0 as f32; parent.ranges.len() + 1];
+        let mut counts = vec![0; parent.ranges.len() + 1];
+        for (r_index, score) in scores.iter().enumerate() {
+            let group = self.parent_range_map[r_index] as usize;
+            sums[group] += score;
+            counts[group] += 1;
+        }
+        sums.iter()
+            .zip(counts)
+            .map(|(sum, count)| if count == 0 { 0.0 } else { sum / count as f32 })
+            .collect()
+    }
+
+    // Levels for scores on this org's ladder, without confidence bounds or waits.
+    pub fn levels_for(&self, scores: &[f32]) -> Vec<u8> {
+        scores
+            .iter()
+            .zip(self.levels.iter())
+            .map(|(score, thresholds)| thresholds.iter().take_while(|l| *l < score).count() as u8)
+            .collect()
+    }
+
+    // A default `fee_mint` means the registration fee is charged in SOL.
+    pub fn fee_in_sol(&self) -> bool {
+        self.fee_mint == Pubkey::default()
+    }
+
+    pub fn pays_level_rewards(&self) -> bool {
+        self.reward_mint != Pubkey::default()
+    }
+
+    // Commit-reveal cycles are `review_period` long and numbered from the epoch.
+    pub fn review_cycle(&self, now: i64) -> i64 {
+        now / self.review_period
+    }
+
+    pub fn cycle_end(&self, cycle: i64) -> i64 {
+        (cycle + 1) * self.review_period
+    }
+
+    pub fn reveal_start(&self, cycle: i64) -> i64 {
+        self.cycle_end(cycle) - self.reveal_window
+    }
+
+    // How many reviews a review counts as for each criterion: the relationship's
+    // multiplier times the entry for the reviewer's level in the criterion's range
+    // group. Levels past the end of the table use its last entry. Self assessments
+    // get all zeros unless the org counts them.
+    pub fn review_weights(
+        &self,
+        reviewer_levels: Option<&[u8]>,
+        relationship: Relationship,
+    ) -> Vec<u16> {
+        if relationship == Relationship::SelfAssessment && !self.include_self_reviews {
+            return vec![0; self.weights.len()];
+        }
+        let multiplier = self.relationship_weights[relationship as usize];
+        let mut weights = vec![multiplier; self.weights.len()];
+        let (Some(reviewer_levels), Some(last)) = (reviewer_levels, self.reputation_weights.last())
+        else {
+            return weights;
+        };
+        for (r_index, group) in self.range_groups().into_iter().enumerate() {
+            let level = reviewer_levels[r_index] as usize;
+            let weight = *self.reputation_weights.get(level).unwrap_or(last);
+            for p1 in group {
+                weights[p1] = weight * multiplier;
+            }
+        }
+        weights
+    }
+
+    pub fn range_groups(&self) -> Vec<Range<usize>> {
+        let mut groups = vec![];
+        let mut start = 0;
+        for end in self
+            .ranges
+            .iter()
+            .map(|&r| r as usize)
+            .chain(std::iter::once(self.weights.len()))
+        {
+            groups.push(start..end);
+            start = end;
+        }
+        groups
+    }
 }
 
 impl<'info> Realloc<'info> for Account<'info, Org> {
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use super::{Org, Score};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ScoreSnapshot {
+    pub scores_sum: Vec<f32>,
+    pub reviews_recieved: Vec<u16>,
+    pub weighted_reviews: Vec<u32>,
+    pub last_update: i64,
+    pub levels: Vec<u8>,
+}
+
+impl ScoreSnapshot {
+    pub fn space(org: &Org) -> usize {
+        (4 + 4 * org.weights.len())
+            + (4 + 2 * org.weights.len())
+            + (4 + 4 * org.weights.len())
+            + 8
+            + (4 + org.levels.len())
+    }
+}
+
+impl From<&Score> for ScoreSnapshot {
+    fn from(score: &Score) -> Self {
+        Self {
+            scores_sum: score.scores_sum.clone(),
+            reviews_recieved: score.reviews_recieved.clone(),
+            weighted_reviews: score.weighted_reviews.clone(),
+            last_update: score.last_update,
+            levels: score.levels.clone(),
+        }
+    }
+}
+
+#[account]
+pub struct OverrideRecord {
+    pub org: Pubkey,
+    pub member: Pubkey,
+    pub sequence: u32,
+    pub signer: Pubkey,
+    pub reason_hash: [u8; 32],
+    pub timestamp: i64,
+    pub override_levels: bool,
+    pub before: ScoreSnapshot,
+    pub after: ScoreSnapshot,
+    pub bump: u8,
+}
+
+impl OverrideRecord {
+    pub fn space(org: &Org) -> usize {
+        8 + 32 + 32 + 4 + 32 + 32 + 8 + 1 + 2 * ScoreSnapshot::space(org) + 1
+    }
+}
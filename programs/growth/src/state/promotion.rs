@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use super::Org;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum PromotionStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+#[account]
+pub struct PendingPromotion {
+    pub score: Pubkey,
+    pub old_levels: Vec<u8>,
+    pub new_levels: Vec<u8>,
+    pub requested_at: i64,
+    pub requested_by: Pubkey,
+    pub status: PromotionStatus,
+    pub reviewed_by: Pubkey,
+    pub reviewed_at: i64,
+    pub reason_hash: [u8; 32],
+    pub bump: u8,
+}
+
+impl PendingPromotion {
+    pub fn space(org: &Org) -> usize {
+        8 + 32 + (4 + org.levels.len()) * 2 + 8 + 32 + 1 + 32 + 8 + 32 + 1
+    }
+
+    pub fn request(
+        &mut self,
+        score: Pubkey,
+        old_levels: Vec<u8>,
+        new_levels: Vec<u8>,
+        requested_at: i64,
+        requested_by: Pubkey,
+        bump: u8,
+    ) {
+        // anyone can crank evaluate_level, a repeat of the pending request must not
+        // reset who asked and when
+        if self.status == PromotionStatus::Pending
+            && self.score == score
+            && self.old_levels == old_levels
+            && self.new_levels == new_levels
+        {
+            msg!(
+                "promotion already pending {:?} -> {:?}",
+                old_levels,
+                new_levels
+            );
+            return;
+        }
+        msg!("promotion pending {:?} -> {:?}", old_levels, new_levels);
+        self.score = score;
+        self.old_levels = old_levels;
+        self.new_levels = new_levels;
+        self.requested_at = requested_at;
+        self.requested_by = requested_by;
+        self.status = PromotionStatus::Pending;
+        self.reviewed_by = Pubkey::default();
+        self.reviewed_at = 0;
+        self.reason_hash = [0; 32];
+        self.bump = bump;
+    }
+}
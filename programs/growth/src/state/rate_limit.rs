@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use super::Org;
+
+#[account]
+pub struct ReviewCounter {
+    pub period_start: i64,
+    pub count: u16,
+    pub last_review: i64,
+    pub bump: u8,
+}
+
+impl ReviewCounter {
+    pub const SPACE: usize = 8 + 8 + 2 + 8 + 1;
+
+    // A period (or cycle) of 0 never rolls over, so the limits become lifetime caps.
+    pub fn record(&mut self, period: i64, now: i64) {
+        if period > 0 && now >= self.period_start + period {
+            self.period_start = now;
+            self.count = 0;
+        }
+        self.count = self.count.saturating_add(1);
+        self.last_review = now;
+    }
+}
+
+pub fn enforce_review_limits(
+    org: &Org,
+    reviewer: &mut ReviewCounter,
+    pair: &mut ReviewCounter,
+    now: i64,
+) {
+    if org.min_review_interval > 0 && pair.last_review != 0 {
+        assert!(now - pair.last_review >= org.min_review_interval);
+    }
+    reviewer.record(org.reviewer_cycle, now);
+    pair.record(org.review_period, now);
+    if org.max_reviews_per_cycle > 0 {
+        assert!(reviewer.count <= org.max_reviews_per_cycle);
+    }
+    if org.max_pair_reviews_per_period > 0 {
+        assert!(pair.count <= org.max_pair_reviews_per_period);
+    }
+}
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+
+use super::{Org, Score};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum Relationship {
+    Manager,
+    #[default]
+    Peer,
+    DirectReport,
+    SelfAssessment,
+}
+
+pub const RELATIONSHIP_TYPES: usize = 4;
+
+pub struct SubmittedReview {
+    pub scores: Vec<f32>,
+    pub weights: Vec<u16>,
+    pub relationship: Relationship,
+}
+
+#[account]
+pub struct ReviewRecord {
+    pub score: Pubkey,
+    pub reviewer: Pubkey,
+    pub index: u32,
+    pub scores: Vec<f32>,
+    pub weights: Vec<u16>,
+    pub relationship: Relationship,
+    pub submitted_at: i64,
+    pub recorded_at: i64,
+    pub revoked: bool,
+    pub revoked_by: Pubkey,
+    // counted in the reviewer's `reviews_sent`
+    pub sent_counted: bool,
+    // the reveal batch of a commit-reveal review, default for direct reviews
+    pub reveal_batch: Pubkey,
+    pub rewarded: bool,
+    pub reward_paid: u64,
+    pub bump: u8,
+}
+
+impl ReviewRecord {
+    pub fn space(org: &Org) -> usize {
+        8 + 32
+            + 32
+            + 4
+            + (4 + 4 * org.weights.len())
+            + (4 + 2 * org.weights.len())
+            + 1
+            + 8
+            + 8
+            + 1
+            + 32
+            + 1
+            + 32
+            + 1
+            + 8
+            + 1
+    }
+
+    pub fn new(
+        score: &Account<'_, Score>,
+        reviewer: Pubkey,
+        review: &SubmittedReview,
+        submitted_at: i64,
+        recorded_at: i64,
+        bump: u8,
+    ) -> Self {
+        Self {
+            score: score.key(),
+            reviewer,
+            index: score.review_count,
+            scores: review.scores.clone(),
+            weights: review.weights.clone(),
+            relationship: review.relationship,
+            submitted_at,
+            recorded_at,
+            revoked: false,
+            revoked_by: Pubkey::default(),
+            sent_counted: false,
+            reveal_batch: Pubkey::default(),
+            rewarded: false,
+            reward_paid: 0,
+            bump,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum DisputeStatus {
+    Open,
+    Upheld,
+    Dismissed,
+}
+
+#[account]
+pub struct Dispute {
+    pub review: Pubkey,
+    pub score: Pubkey,
+    pub opened_by: Pubkey,
+    pub reason_hash: [u8; 32],
+    pub opened_at: i64,
+    pub status: DisputeStatus,
+    pub resolved_by: Pubkey,
+    pub resolved_at: i64,
+    pub resolution_hash: [u8; 32],
+    pub bump: u8,
+}
+
+impl Dispute {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 32 + 8 + 1 + 32 + 8 + 32 + 1;
+}
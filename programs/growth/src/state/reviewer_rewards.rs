@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use super::Org;
+
+#[account]
+pub struct ReviewerRewards {
+    pub org: Pubkey,
+    pub reviewer: Pubkey,
+    pub period_start: i64,
+    pub period_earned: u64,
+    pub total_earned: u64,
+    pub total_clawed_back: u64,
+    pub clawback_owed: u64,
+    pub bump: u8,
+}
+
+impl ReviewerRewards {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 1;
+
+    pub fn ensure_initialized(&mut self, org: Pubkey, reviewer: Pubkey, bump: u8) {
+        if self.reviewer == Pubkey::default() {
+            self.org = org;
+            self.reviewer = reviewer;
+            self.bump = bump;
+        }
+    }
+
+    // Returns the amount earned for one review and the part of it to transfer.
+    // Earnings are capped per review period and anything clawed back is taken out
+    // of them before paying.
+    pub fn earn(&mut self, org: &Org, now: i64) -> (u64, u64) {
+        if org.review_period > 0 && now >= self.period_start + org.review_period {
+            self.period_start = now;
+            self.period_earned = 0;
+        }
+        let mut amount = org.review_reward;
+        if org.max_review_rewards_per_period > 0 {
+            amount = amount.min(
+                org.max_review_rewards_per_period
+                    .saturating_sub(self.period_earned),
+            );
+        }
+        self.period_earned += amount;
+        let offset = amount.min(self.clawback_owed);
+        self.clawback_owed -= offset;
+        self.total_earned += amount;
+        (amount, amount - offset)
+    }
+
+    pub fn claw_back(&mut self, amount: u64) {
+        assert!(self.total_clawed_back + amount <= self.total_earned);
+        self.total_clawed_back += amount;
+        self.clawback_owed += amount;
+    }
+}
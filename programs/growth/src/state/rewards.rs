@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use super::Org;
+
+#[account]
+pub struct RewardClaims {
+    pub score: Pubkey,
+    pub claimed: Vec<u8>,
+    // reached but not paid yet, see `claim_level_rewards`
+    pub owed: u64,
+    pub bump: u8,
+}
+
+impl RewardClaims {
+    pub fn space(org: &Org) -> usize {
+        8 + 32 + 4 + org.levels.len() + 8 + 1
+    }
+
+    // Returns the amount owed for every level in `new_levels` above the highest one
+    // already paid. The first call starts from `old_levels`, so starting levels and
+    // levels reached before rewards were enabled are never paid.
+    pub fn claim(
+        &mut self,
+        org: &Org,
+        score: Pubkey,
+        old_levels: &[u8],
+        new_levels: &[u8],
+        bump: u8,
+    ) -> u64 {
+        if self.score == Pubkey::default() {
+            self.score = score;
+            self.claimed = old_levels.to_vec();
+            self.bump = bump;
+        }
+        let mut amount = 0;
+        for (p1, level) in new_levels.iter().enumerate() {
+            let rewards = org
+                .level_rewards
+                .get(p1)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            while self.claimed[p1] < *level {
+                // levels past the end of the reward table pay nothing
+                amount += rewards.get(self.claimed[p1] as usize).copied().unwrap_or(0);
+                self.claimed[p1] += 1;
+            }
+        }
+        amount
+    }
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use super::{MemberAggregate, Org};
+
+// A child org's members as seen on its parent's ladder. Scores and levels are
+// in the parent's range groups and dimensions.
+#[account]
+pub struct OrgRollup {
+    pub parent: Pubkey,
+    pub org: Pubkey,
+    pub aggregate: MemberAggregate,
+    // the org's `parent_epoch` the aggregate was built under
+    pub epoch: u32,
+    pub bump: u8,
+}
+
+impl OrgRollup {
+    pub fn space(parent: &Org) -> usize {
+        8 + 32 + 32 + MemberAggregate::space(parent) + 4 + 1
+    }
+
+    pub fn ensure_initialized(&mut self, parent: &Account<Org>, org: Pubkey, bump: u8) {
+        if self.parent == Pubkey::default() {
+            self.parent = parent.key();
+            self.org = org;
+            self.aggregate = MemberAggregate::new(parent);
+            self.bump = bump;
+        }
+    }
+
+    // Drops every member's contribution, they're added back as they get rolled up
+    // under the new epoch.
+    pub fn reset(&mut self, parent: &Org, epoch: u32) {
+        self.aggregate = MemberAggregate::new(parent);
+        self.epoch = epoch;
+    }
+}
+
+// The member's last rolled up scores and levels, i.e. what they currently
+// contribute to the `OrgRollup`. Empty until the member is first rolled up.
+#[account]
+pub struct RolledUpScore {
+    pub parent: Pubkey,
+    pub score: Pubkey,
+    pub scores: Vec<f32>,
+    pub levels: Vec<u8>,
+    pub last_update: i64,
+    pub epoch: u32,
+    pub bump: u8,
+}
+
+impl RolledUpScore {
+    pub fn space(parent: &Org) -> usize {
+        8 + 32 + 32 + (4 + 4 * (parent.ranges.len() + 1)) + (4 + parent.levels.len()) + 8 + 4 + 1
+    }
+}
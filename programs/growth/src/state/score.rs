@@ -2,7 +2,10 @@ use anchor_lang::{prelude::*, system_program};
 
 use crate::utils::Realloc;
 
-use super::Org;
+use super::{
+    group_standard_error, group_variance, CriterionStats, Org, Relationship, RevealBatch,
+    ScoringStrategy, RELATIONSHIP_TYPES,
+};
 
 #[account]
 pub struct Score {
@@ -16,6 +19,25 @@ pub struct Score {
     pub levels: Vec<u8>,
     pub last_update: i64,
     pub bump: u8,
+    pub override_count: u32,
+    pub last_override_ts: i64,
+    pub pending: bool,
+    pub review_nonce: u64,
+    pub sent_period_start: i64,
+    pub period_reviews_sent: u16,
+    pub review_count: u32,
+    pub relationship_sums: Vec<f32>,
+    pub relationship_counts: Vec<u16>,
+    pub manager: Pubkey,
+    pub team: Pubkey,
+    pub scores_min: Vec<f32>,
+    pub scores_max: Vec<f32>,
+    pub scores_sq_sum: Vec<f32>,
+    // `reviews_recieved` weighted, the divisor of `scores_sum`
+    pub weighted_reviews: Vec<u32>,
+    // weights of the reviews that set `scores_min` and `scores_max`
+    pub scores_min_weight: Vec<u16>,
+    pub scores_max_weight: Vec<u16>,
 }
 
 impl Score {
@@ -26,48 +48,304 @@ impl Score {
             scores_sum: vec![],
             reviews_recieved: vec![],
             reviews_sent: 0,
-            applicant: Pubkey::new_from_array([0; 32 as usize]),
-            mint: Pubkey::new_from_array([0; 32 as usize]),
+            applicant: Pubkey::new_from_array([0; 32]),
+            mint: Pubkey::new_from_array([0; 32]),
             levels: vec![],
             last_update: 0,
             bump,
+            override_count: 0,
+            last_override_ts: 0,
+            pending: false,
+            review_nonce: 0,
+            sent_period_start: 0,
+            period_reviews_sent: 0,
+            review_count: 0,
+            relationship_sums: vec![],
+            relationship_counts: vec![],
+            manager: Pubkey::default(),
+            team: Pubkey::default(),
+            scores_min: vec![],
+            scores_max: vec![],
+            scores_sq_sum: vec![],
+            weighted_reviews: vec![],
+            scores_min_weight: vec![],
+            scores_max_weight: vec![],
         }
     }
 
-    pub fn update_scores(&mut self, org: &Account<'_, Org>) {
-        msg!("Calculating new score");
-        let mut r_index = 0;
-        let mut group_sum = 0 as f32;
-        let mut counter = 0 as f32;
-        let mut next;
-        for (p1, _) in self.scores_sum.clone().iter().enumerate() {
-            if r_index > org.ranges.len() - 1 {
-                next = org.weights.len() as u8;
-            } else {
-                next = org.ranges[r_index];
+    pub fn space_to_add(org: &Org, name: &str) -> usize {
+        4 * (org.ranges.len() + 1) // scores
+        + (4 * org.weights.len())  //scores_sum
+        + (8 * org.weights.len()) // scores_min, scores_max
+        + (4 * org.weights.len()) // scores_sq_sum
+        + org.levels.len() // levels
+        + (name.len() * 4) // name
+        + (6 * org.weights.len()) // reviews_recieved, weighted_reviews
+        + (4 * org.weights.len()) // scores_min_weight, scores_max_weight
+        + (6 * RELATIONSHIP_TYPES * org.weights.len()) // relationship_sums, relationship_counts
+    }
+
+    // Fields are only ever appended, so an account from before some of them existed
+    // reads them as zeros and empty vectors. `migrate_score` fills those in, checking
+    // the last one is enough.
+    pub fn is_migrated(&self, org: &Org) -> bool {
+        self.scores_max_weight.len() == org.weights.len()
+    }
+
+    // Review stats the account didn't keep yet are rebuilt as after a bulk
+    // overwrite. Relationship totals stay empty and are skipped.
+    pub fn migrate(&mut self, org: &Org) {
+        self.scores_min = vec![0 as f32; org.weights.len()];
+        self.scores_max = vec![0 as f32; org.weights.len()];
+        self.scores_sq_sum = vec![0 as f32; org.weights.len()];
+        self.weighted_reviews = vec![0; org.weights.len()];
+        self.scores_min_weight = vec![0; org.weights.len()];
+        self.scores_max_weight = vec![0; org.weights.len()];
+        self.reset_review_stats();
+    }
+
+    pub fn init_member(
+        &mut self,
+        org: &Org,
+        name: String,
+        applicant: Pubkey,
+        levels: Vec<u8>,
+        last_update: i64,
+    ) {
+        self.scores = vec![0 as f32; org.ranges.len() + 1];
+        self.levels = levels;
+        self.reviews_recieved = vec![0; org.weights.len()];
+        self.weighted_reviews = vec![0; org.weights.len()];
+        self.name = name;
+        self.last_update = last_update;
+        msg!("last update:{}", last_update);
+        self.applicant = applicant;
+        self.scores_sum = vec![0 as f32; org.weights.len()];
+        self.scores_min = vec![0 as f32; org.weights.len()];
+        self.scores_max = vec![0 as f32; org.weights.len()];
+        self.scores_sq_sum = vec![0 as f32; org.weights.len()];
+        self.scores_min_weight = vec![0; org.weights.len()];
+        self.scores_max_weight = vec![0; org.weights.len()];
+        self.relationship_sums = vec![0 as f32; RELATIONSHIP_TYPES * org.weights.len()];
+        self.relationship_counts = vec![0; RELATIONSHIP_TYPES * org.weights.len()];
+    }
+
+    // A review with weight `w` counts as `w` identical reviews, so the sums and
+    // `weighted_reviews` are weighted accumulators. `reviews_recieved` counts it once.
+    pub fn record_review(&mut self, scores: &[f32], weights: &[u16]) {
+        for (p1, e1) in scores.iter().enumerate() {
+            let weight = weights[p1] as f32;
+            self.scores_sum[p1] += e1 * weight;
+            if *e1 != 0 as f32 && weights[p1] != 0 {
+                self.widen_range(p1, *e1, weights[p1], *e1, weights[p1]);
+                self.scores_sq_sum[p1] += e1 * e1 * weight;
+                self.reviews_recieved[p1] += 1;
+                self.weighted_reviews[p1] += weights[p1] as u32;
+            }
+        }
+    }
+
+    // Takes the new extremes along with the weights of the reviews behind them, must
+    // run before the counts are increased.
+    fn widen_range(&mut self, p1: usize, min: f32, min_weight: u16, max: f32, max_weight: u16) {
+        if self.reviews_recieved[p1] == 0 {
+            self.scores_min[p1] = min;
+            self.scores_min_weight[p1] = min_weight;
+            self.scores_max[p1] = max;
+            self.scores_max_weight[p1] = max_weight;
+            return;
+        }
+        if min < self.scores_min[p1] {
+            self.scores_min[p1] = min;
+            self.scores_min_weight[p1] = min_weight;
+        }
+        if max > self.scores_max[p1] {
+            self.scores_max[p1] = max;
+            self.scores_max_weight[p1] = max_weight;
+        }
+    }
+
+    pub fn is_manager(&self, key: &Pubkey) -> bool {
+        self.manager != Pubkey::default() && self.manager == *key
+    }
+
+    // Reviews from the member's manager always count as manager reviews, whatever
+    // relationship the reviewer picked, and nobody else can claim to be the manager.
+    pub fn review_relationship(&self, reviewer: &Pubkey, claimed: Relationship) -> Relationship {
+        if self.is_manager(reviewer) {
+            Relationship::Manager
+        } else if claimed == Relationship::Manager {
+            Relationship::Peer
+        } else {
+            claimed
+        }
+    }
+
+    // Unweighted totals per relationship type and criterion, indexed
+    // `relationship * criteria + criterion`. Members registered before these were
+    // added have empty vectors and skip them.
+    pub fn record_relationship_review(&mut self, relationship: Relationship, scores: &[f32]) {
+        if self.relationship_sums.is_empty() {
+            return;
+        }
+        let offset = relationship as usize * scores.len();
+        for (p1, e1) in scores.iter().enumerate() {
+            if *e1 != 0 as f32 {
+                self.relationship_sums[offset + p1] += e1;
+                self.relationship_counts[offset + p1] += 1;
             }
-            if self.reviews_recieved[p1] != 0 {
-                group_sum +=
-                    self.scores_sum[p1] * org.weights[p1] / self.reviews_recieved[p1] as f32;
-                counter += org.weights[p1];
+        }
+    }
+
+    pub fn remove_relationship_review(&mut self, relationship: Relationship, scores: &[f32]) {
+        if self.relationship_sums.is_empty() {
+            return;
+        }
+        let offset = relationship as usize * scores.len();
+        for (p1, e1) in scores.iter().enumerate() {
+            if *e1 != 0 as f32 {
+                self.relationship_sums[offset + p1] -= e1;
+                self.relationship_counts[offset + p1] -= 1;
             }
-            if p1 >= next as usize - 1 {
-                let avg = group_sum / counter as f32;
-                self.scores[r_index] = avg;
-                r_index += 1;
-                group_sum = 0 as f32;
-                counter = 0 as f32;
+        }
+    }
+
+    pub fn merge_batch(&mut self, batch: &RevealBatch) {
+        for p1 in 0..batch.scores_sum.len() {
+            self.scores_sum[p1] += batch.scores_sum[p1];
+            if batch.reviews_recieved[p1] == 0 {
+                continue;
+            }
+            self.widen_range(
+                p1,
+                batch.scores_min[p1],
+                batch.scores_min_weight[p1],
+                batch.scores_max[p1],
+                batch.scores_max_weight[p1],
+            );
+            self.scores_sq_sum[p1] += batch.scores_sq_sum[p1];
+            self.reviews_recieved[p1] += batch.reviews_recieved[p1];
+            self.weighted_reviews[p1] += batch.weighted_reviews[p1];
+        }
+        if !self.relationship_sums.is_empty() {
+            for (p1, sum) in batch.relationship_sums.iter().enumerate() {
+                self.relationship_sums[p1] += sum;
+                self.relationship_counts[p1] += batch.relationship_counts[p1];
             }
         }
     }
 
+    // Min and max can't be narrowed back without the remaining reviews. When the
+    // removed review was one of them both fall back to the remaining average with no
+    // weight, so the trimmed mean stops dropping a value that is gone until new reviews
+    // widen them.
+    // A bulk overwrite after the review may have left fewer reviews than it removes,
+    // so the counts saturate and an emptied criterion is cleared.
+    pub fn remove_review(&mut self, scores: &[f32], weights: &[u16]) {
+        for (p1, e1) in scores.iter().enumerate() {
+            let weight = weights[p1] as f32;
+            self.scores_sum[p1] -= e1 * weight;
+            if *e1 != 0 as f32 && weights[p1] != 0 {
+                self.scores_sq_sum[p1] = (self.scores_sq_sum[p1] - e1 * e1 * weight).max(0.0);
+                self.reviews_recieved[p1] = self.reviews_recieved[p1].saturating_sub(1);
+                self.weighted_reviews[p1] =
+                    self.weighted_reviews[p1].saturating_sub(weights[p1] as u32);
+                if self.weighted_reviews[p1] == 0 {
+                    self.scores_sum[p1] = 0.0;
+                    self.scores_min[p1] = 0.0;
+                    self.scores_max[p1] = 0.0;
+                    self.scores_sq_sum[p1] = 0.0;
+                } else if *e1 <= self.scores_min[p1] || *e1 >= self.scores_max[p1] {
+                    let avg = self.scores_sum[p1] / self.weighted_reviews[p1] as f32;
+                    self.scores_min[p1] = avg;
+                    self.scores_max[p1] = avg;
+                    self.scores_min_weight[p1] = 0;
+                    self.scores_max_weight[p1] = 0;
+                }
+            }
+        }
+    }
+
+    // Individual reviews aren't stored, so after a bulk overwrite every review is assumed
+    // to equal the criterion average: trimming leaves it unchanged and the spread is zero.
+    // The overwritten counts are taken as unweighted.
+    pub fn reset_review_stats(&mut self) {
+        for (p1, sum) in self.scores_sum.iter().enumerate() {
+            self.weighted_reviews[p1] = self.reviews_recieved[p1] as u32;
+            let avg = if self.reviews_recieved[p1] != 0 {
+                sum / self.reviews_recieved[p1] as f32
+            } else {
+                0.0
+            };
+            self.scores_min[p1] = avg;
+            self.scores_max[p1] = avg;
+            self.scores_min_weight[p1] = 0;
+            self.scores_max_weight[p1] = 0;
+            self.scores_sq_sum[p1] = avg * avg * self.reviews_recieved[p1] as f32;
+        }
+    }
+
+    pub fn criterion_stats(&self, org: &Org, p1: usize) -> CriterionStats {
+        CriterionStats {
+            weight: org.weights[p1],
+            sum: self.scores_sum[p1],
+            count: self.weighted_reviews[p1],
+            reviews: self.reviews_recieved[p1] as u32,
+            min: self.scores_min[p1],
+            min_weight: self.scores_min_weight[p1] as u32,
+            max: self.scores_max[p1],
+            max_weight: self.scores_max_weight[p1] as u32,
+            sum_sq: self.scores_sq_sum[p1],
+        }
+    }
+
+    pub fn update_scores(&mut self, org: &Account<'_, Org>) {
+        msg!("Calculating new score");
+        for (r_index, criteria) in self.group_stats(org).iter().enumerate() {
+            self.scores[r_index] = org.scoring_method.aggregate(criteria);
+        }
+    }
+
+    fn group_stats(&self, org: &Org) -> Vec<Vec<CriterionStats>> {
+        org.range_groups()
+            .into_iter()
+            .map(|group| group.map(|p1| self.criterion_stats(org, p1)).collect())
+            .collect()
+    }
+
+    pub fn variance(&self, org: &Org) -> Vec<f32> {
+        self.group_stats(org)
+            .iter()
+            .map(|c| group_variance(c))
+            .collect()
+    }
+
+    pub fn standard_error(&self, org: &Org) -> Vec<f32> {
+        self.group_stats(org)
+            .iter()
+            .map(|c| group_standard_error(c))
+            .collect()
+    }
+
     pub fn calculate_potential_level(&self, org: &Account<'_, Org>) -> Vec<u8> {
         msg!("Upading potential levels");
-        let mut levels: Vec<u8> = vec![0 as u8; self.levels.len() as usize];
+        let mut levels: Vec<u8> = vec![0; self.levels.len()];
+        // Levels above the current one are only credited once the lower end of the
+        // confidence interval clears the threshold.
+        let standard_error = if org.confidence_z > 0.0 {
+            self.standard_error(org)
+        } else {
+            vec![]
+        };
         for (p1, e1) in self.scores.iter().enumerate() {
             let mut level = 0;
-            for l in org.levels[p1].iter() {
-                if l < e1 {
+            for (l_index, l) in org.levels[p1].iter().enumerate() {
+                let credited = if standard_error.is_empty() || l_index < self.levels[p1] as usize {
+                    l < e1
+                } else {
+                    *l < e1 - org.confidence_z * standard_error[p1]
+                };
+                if credited {
                     level += 1
                 } else {
                     break;
@@ -76,7 +354,7 @@ impl Score {
             levels[p1] = level;
         }
         msg!("potential levels:{:?}", levels);
-        return levels;
+        levels
     }
 
     pub fn calculate_next_level(&self, potential_levels: Vec<u8>) -> Vec<u8> {
@@ -92,13 +370,62 @@ impl Score {
             }
         }
         msg!("next levels:{:?}", levels);
-        return levels;
+        levels
+    }
+
+    pub fn level_change_due(&self, org: &Account<'_, Org>, submission_ts: i64) -> bool {
+        self.last_update + (org.level_wait as i64) < submission_ts
+            && *self.reviews_recieved.iter().max().unwrap() >= org.min_reviews as u16
+    }
+
+    pub fn record_review_sent(&mut self, org: &Org, now: i64) {
+        if org.review_period > 0 && now >= self.sent_period_start + org.review_period {
+            self.sent_period_start = now;
+            self.period_reviews_sent = 0;
+        }
+        self.reviews_sent += 1;
+        self.period_reviews_sent += 1;
+    }
+
+    // Undoes `record_review_sent` for a revoked review, the period count only if the
+    // review was sent in the current period.
+    pub fn remove_review_sent(&mut self, sent_at: i64) {
+        self.reviews_sent = self.reviews_sent.saturating_sub(1);
+        if sent_at >= self.sent_period_start {
+            self.period_reviews_sent = self.period_reviews_sent.saturating_sub(1);
+        }
+    }
+
+    // Reviews sent that count towards `min_reviews_sent`, either all of them or only
+    // the ones sent during the current review period.
+    pub fn counted_reviews_sent(&self, org: &Org, now: i64) -> u16 {
+        if !org.reviews_sent_per_period {
+            self.reviews_sent
+        } else if org.review_period > 0 && now >= self.sent_period_start + org.review_period {
+            0
+        } else {
+            self.period_reviews_sent
+        }
+    }
+
+    pub fn is_promotion(&self, next_levels: &[u8]) -> bool {
+        next_levels
+            .iter()
+            .zip(&self.levels)
+            .any(|(next, current)| next > current)
+    }
+
+    // Demotions are never held back, promotions wait until the member has reviewed
+    // enough peers.
+    pub fn can_change_to(&self, org: &Org, next_levels: &[u8], now: i64) -> bool {
+        !self.is_promotion(next_levels)
+            || self.counted_reviews_sent(org, now) >= org.min_reviews_sent
     }
 
     pub fn reconcile(&mut self, org: &Account<'_, Org>) -> Vec<u8> {
         self.update_scores(org);
         let potential_levels = self.calculate_potential_level(org);
-        return self.calculate_next_level(potential_levels);
+        self.calculate_next_level(potential_levels)
     }
 }
 
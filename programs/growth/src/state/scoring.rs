@@ -0,0 +1,187 @@
+use anchor_lang::prelude::*;
+
+pub struct CriterionStats {
+    pub weight: f32,
+    pub sum: f32,
+    // weighted
+    pub count: u32,
+    // unweighted
+    pub reviews: u32,
+    pub min: f32,
+    pub min_weight: u32,
+    pub max: f32,
+    pub max_weight: u32,
+    pub sum_sq: f32,
+}
+
+impl CriterionStats {
+    pub fn variance(&self) -> f32 {
+        if self.reviews < 2 {
+            return f32::NAN;
+        }
+        let n = self.count as f32;
+        ((self.sum_sq - self.sum * self.sum / n) / (n - 1.0)).max(0.0)
+    }
+}
+
+/// Weighted mean of the per-criterion sample variances.
+pub fn group_variance(criteria: &[CriterionStats]) -> f32 {
+    let mut group_sum = 0.0;
+    let mut counter = 0.0;
+    for c in criteria.iter().filter(|c| c.count != 0) {
+        group_sum += c.variance() * c.weight;
+        counter += c.weight;
+    }
+    group_sum / counter
+}
+
+/// Standard error of the weighted mean of the per-criterion averages, NaN while any
+/// reviewed criterion has fewer than two reviews.
+pub fn group_standard_error(criteria: &[CriterionStats]) -> f32 {
+    let total_weight: f32 = criteria
+        .iter()
+        .filter(|c| c.count != 0)
+        .map(|c| c.weight)
+        .sum();
+    let mut sq_error = 0.0;
+    for c in criteria.iter().filter(|c| c.count != 0) {
+        let share = c.weight / total_weight;
+        sq_error += share * share * c.variance() / c.reviews as f32;
+    }
+    sq_error.sqrt()
+}
+
+pub trait ScoringStrategy {
+    fn aggregate(&self, criteria: &[CriterionStats]) -> f32;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum ScoringMethod {
+    #[default]
+    WeightedMean,
+    WeightedMedian,
+    TrimmedMean,
+    BayesianAverage {
+        prior_mean: f32,
+        prior_weight: f32,
+    },
+}
+
+impl ScoringStrategy for ScoringMethod {
+    fn aggregate(&self, criteria: &[CriterionStats]) -> f32 {
+        match *self {
+            ScoringMethod::WeightedMean => WeightedMean.aggregate(criteria),
+            ScoringMethod::WeightedMedian => WeightedMedian.aggregate(criteria),
+            ScoringMethod::TrimmedMean => TrimmedMean.aggregate(criteria),
+            ScoringMethod::BayesianAverage {
+                prior_mean,
+                prior_weight,
+            } => BayesianAverage {
+                prior_mean,
+                prior_weight,
+            }
+            .aggregate(criteria),
+        }
+    }
+}
+
+// Scores are stored, so a group without any reviews (for instance after its only
+// review was revoked) aggregates to 0 rather than NaN.
+fn mean_or_zero(sum: f32, weight: f32) -> f32 {
+    if weight == 0.0 {
+        0.0
+    } else {
+        sum / weight
+    }
+}
+
+/// Weighted mean of the per-criterion averages, criteria without reviews are skipped.
+pub struct WeightedMean;
+
+impl ScoringStrategy for WeightedMean {
+    fn aggregate(&self, criteria: &[CriterionStats]) -> f32 {
+        let mut group_sum = 0.0;
+        let mut counter = 0.0;
+        for c in criteria.iter() {
+            if c.count != 0 {
+                group_sum += c.sum * c.weight / c.count as f32;
+                counter += c.weight;
+            }
+        }
+        mean_or_zero(group_sum, counter)
+    }
+}
+
+/// Weighted median of the per-criterion averages.
+pub struct WeightedMedian;
+
+impl ScoringStrategy for WeightedMedian {
+    fn aggregate(&self, criteria: &[CriterionStats]) -> f32 {
+        let mut averages: Vec<(f32, f32)> = criteria
+            .iter()
+            .filter(|c| c.count != 0)
+            .map(|c| (c.sum / c.count as f32, c.weight))
+            .collect();
+        averages.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let half = averages.iter().map(|a| a.1).sum::<f32>() / 2.0;
+        let mut cumulative = 0.0;
+        for (avg, weight) in averages.iter() {
+            cumulative += weight;
+            if cumulative >= half {
+                return *avg;
+            }
+        }
+        0.0
+    }
+}
+
+/// Weighted mean where each criterion drops its highest and lowest review, with their weights,
+/// once it has at least three. When the extremes aren't known any more (after an override or a
+/// revoked review) `Score` keeps min and max at the criterion average, so nothing is trimmed.
+pub struct TrimmedMean;
+
+impl ScoringStrategy for TrimmedMean {
+    fn aggregate(&self, criteria: &[CriterionStats]) -> f32 {
+        let mut group_sum = 0.0;
+        let mut counter = 0.0;
+        for c in criteria.iter() {
+            if c.count == 0 {
+                continue;
+            }
+            let trimmed_count = c.count.saturating_sub(c.min_weight + c.max_weight);
+            let avg = if c.reviews >= 3 && c.min < c.max && trimmed_count > 0 {
+                (c.sum - c.min * c.min_weight as f32 - c.max * c.max_weight as f32)
+                    / trimmed_count as f32
+            } else {
+                c.sum / c.count as f32
+            };
+            group_sum += avg * c.weight;
+            counter += c.weight;
+        }
+        mean_or_zero(group_sum, counter)
+    }
+}
+
+/// Weighted mean where each criterion is pulled towards `prior_mean` as if it had
+/// `prior_weight` extra reviews, so a handful of reviews can't swing it far.
+pub struct BayesianAverage {
+    pub prior_mean: f32,
+    pub prior_weight: f32,
+}
+
+impl ScoringStrategy for BayesianAverage {
+    fn aggregate(&self, criteria: &[CriterionStats]) -> f32 {
+        let mut group_sum = 0.0;
+        let mut counter = 0.0;
+        for c in criteria.iter() {
+            let samples = self.prior_weight + c.count as f32;
+            if samples == 0.0 {
+                continue;
+            }
+            let avg = (self.prior_mean * self.prior_weight + c.sum) / samples;
+            group_sum += avg * c.weight;
+            counter += c.weight;
+        }
+        mean_or_zero(group_sum, counter)
+    }
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use super::{MemberAggregate, Org};
+
+pub const MAX_TEAM_NAME_LEN: usize = 32;
+
+#[account]
+pub struct Team {
+    pub org: Pubkey,
+    pub name: String,
+    pub lead: Pubkey,
+    pub aggregate: MemberAggregate,
+    pub bump: u8,
+}
+
+impl Team {
+    pub fn space(org: &Org, name: &str) -> usize {
+        8 + 32 + (4 + name.len()) + 32 + MemberAggregate::space(org) + 1
+    }
+
+    pub fn init(&mut self, org: &Account<Org>, name: String, lead: Pubkey, bump: u8) {
+        self.org = org.key();
+        self.name = name;
+        self.lead = lead;
+        self.aggregate = MemberAggregate::new(org);
+        self.bump = bump;
+    }
+}
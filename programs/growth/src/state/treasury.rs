@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Default, Debug)]
+pub enum FeePayer {
+    #[default]
+    Authority,
+    Applicant,
+}
+
+#[account]
+pub struct Treasury {
+    pub org: Pubkey,
+    pub bump: u8,
+}
+
+impl Treasury {
+    pub const SPACE: usize = 8 + 32 + 1;
+}
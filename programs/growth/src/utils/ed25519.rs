@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{ed25519_program, instruction::Instruction};
+
+// Checks that `ix` is an Ed25519 program instruction verifying a single signature by
+// `pubkey` over exactly `message`, with every offset pointing into its own data.
+pub fn verify_ed25519_ix(ix: &Instruction, pubkey: &Pubkey, message: &[u8]) -> bool {
+    if ix.program_id != ed25519_program::ID || !ix.accounts.is_empty() {
+        return false;
+    }
+    let data = &ix.data;
+    if data.len() < 16 || data[0] != 1 {
+        return false;
+    }
+    let offset = |i: usize| u16::from_le_bytes([data[2 + 2 * i], data[3 + 2 * i]]);
+    let signature_ix = offset(1);
+    let public_key_offset = offset(2) as usize;
+    let public_key_ix = offset(3);
+    let message_offset = offset(4) as usize;
+    let message_size = offset(5) as usize;
+    let message_ix = offset(6);
+    if signature_ix != u16::MAX || public_key_ix != u16::MAX || message_ix != u16::MAX {
+        return false;
+    }
+    data.get(public_key_offset..public_key_offset + 32) == Some(pubkey.as_ref())
+        && data.get(message_offset..message_offset + message_size) == Some(message)
+}
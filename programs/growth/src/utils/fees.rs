@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, TokenAccount};
+
+use crate::{
+    errors::GrowthError,
+    events::RegistrationFeePaid,
+    state::{Org, Treasury},
+};
+
+pub struct FeeAccounts<'a, 'info> {
+    pub payer: AccountInfo<'info>,
+    pub treasury: &'a mut Account<'info, Treasury>,
+    pub treasury_bump: u8,
+    pub fee_token_account: Option<&'a Account<'info, TokenAccount>>,
+    pub treasury_token_account: Option<&'a Account<'info, TokenAccount>>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+pub fn collect_registration_fee(
+    org: &Account<'_, Org>,
+    member: Pubkey,
+    accounts: FeeAccounts<'_, '_>,
+) -> Result<()> {
+    accounts.treasury.org = org.key();
+    accounts.treasury.bump = accounts.treasury_bump;
+    if org.registration_fee == 0 {
+        return Ok(());
+    }
+    if org.fee_in_sol() {
+        system_program::transfer(
+            CpiContext::new(
+                accounts.system_program,
+                system_program::Transfer {
+                    from: accounts.payer.clone(),
+                    to: accounts.treasury.to_account_info(),
+                },
+            ),
+            org.registration_fee,
+        )?;
+    } else {
+        let from = accounts
+            .fee_token_account
+            .ok_or(GrowthError::FeeTokenAccountMissing)?;
+        let to = accounts
+            .treasury_token_account
+            .ok_or(GrowthError::FeeTokenAccountMissing)?;
+        token::transfer(
+            CpiContext::new(
+                accounts.token_program,
+                token::Transfer {
+                    from: from.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: accounts.payer.clone(),
+                },
+            ),
+            org.registration_fee,
+        )?;
+    }
+    emit!(RegistrationFeePaid {
+        org: org.key(),
+        member,
+        payer: accounts.payer.key(),
+        mint: org.fee_mint,
+        amount: org.registration_fee,
+    });
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use anchor_lang::solana_program::keccak;
+
+// Leaves and inner nodes hash under different prefixes, otherwise an inner
+// node could be presented as a leaf of a shorter proof
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+pub fn hash_leaf(data: &[&[u8]]) -> [u8; 32] {
+    let mut parts = vec![LEAF_PREFIX];
+    parts.extend_from_slice(data);
+    keccak::hashv(&parts).0
+}
+
+pub fn verify_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let mut computed = leaf;
+    for node in proof.iter() {
+        computed = if computed <= *node {
+            keccak::hashv(&[NODE_PREFIX, &computed, node]).0
+        } else {
+            keccak::hashv(&[NODE_PREFIX, node, &computed]).0
+        };
+    }
+    computed == root
+}
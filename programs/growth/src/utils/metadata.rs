@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::{
+        create_metadata_accounts_v3, update_metadata_accounts_v2, CreateMetadataAccountsV3,
+        MetadataAccount, UpdateMetadataAccountsV2,
+    },
+    token::{mint_to, MintTo},
+};
+use mpl_token_metadata::state::{Collection, DataV2};
+
+use crate::state::{Org, PAUSE_METADATA};
+
+pub fn update_score_uri<'info>(
+    org: &Account<'info, Org>,
+    metadata: &Account<'info, MetadataAccount>,
+    token_metadata_program: &AccountInfo<'info>,
+    levels: &[u8],
+) -> Result<()> {
+    // levels still change while metadata is paused, `sync_metadata` catches the
+    // uri up afterwards
+    if org.is_paused(PAUSE_METADATA) {
+        msg!("metadata paused, uri not updated");
+        return Ok(());
+    }
+    msg!("Updating NFT");
+    let org_mint = org.mint.key();
+    let signer: &[&[&[u8]]] = &[&[
+        b"org",
+        org_mint.as_ref(),
+        org.authority.as_ref(),
+        &[org.bump],
+    ]];
+    let data_v2 = mpl_token_metadata::state::DataV2 {
+        name: metadata.data.name.to_string(),
+        symbol: "SCORE".to_string(),
+        uri: org.level_uri(levels),
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: Some(Collection {
+            verified: true,
+            key: org.mint.key(),
+        }),
+        uses: None,
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_metadata_program.clone(),
+        UpdateMetadataAccountsV2 {
+            metadata: metadata.to_account_info(),
+            update_authority: org.to_account_info(),
+        },
+        signer,
+    );
+    update_metadata_accounts_v2(cpi_ctx, None, Some(data_v2), Some(true), Some(true))
+}
+
+pub struct ScoreNftAccounts<'info> {
+    pub payer: AccountInfo<'info>,
+    pub register_mint: AccountInfo<'info>,
+    pub token_account: AccountInfo<'info>,
+    pub metadata: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub token_metadata_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>,
+}
+
+pub fn create_score_nft<'info>(
+    org: &Account<'info, Org>,
+    accounts: ScoreNftAccounts<'info>,
+    name: &str,
+    levels: &[u8],
+) -> Result<()> {
+    let mint = org.mint.key();
+    let signer: &[&[&[u8]]] = &[&[b"org", mint.as_ref(), org.authority.as_ref(), &[org.bump]]];
+
+    msg!("Minting token");
+    let mint_to_cpi_accounts = MintTo {
+        mint: accounts.register_mint.clone(),
+        to: accounts.token_account,
+        authority: org.to_account_info(),
+    };
+    let mint_to_cpi_ctx =
+        CpiContext::new_with_signer(accounts.token_program, mint_to_cpi_accounts, signer);
+    mint_to(mint_to_cpi_ctx, 1)?;
+    msg!("Token minted");
+
+    let mut metadata_name = org.name.clone();
+    metadata_name.push_str(" - ");
+    metadata_name.push_str(name);
+    let data_v2 = DataV2 {
+        name: metadata_name.to_string(),
+        symbol: "SCORE".to_string(),
+        uri: org.level_uri(levels),
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: Some(Collection {
+            verified: false,
+            key: org.mint.key(),
+        }),
+        uses: None,
+    };
+    let create_metadata_cpi_accounts = CreateMetadataAccountsV3 {
+        metadata: accounts.metadata,
+        mint: accounts.register_mint,
+        mint_authority: org.to_account_info(),
+        update_authority: org.to_account_info(),
+        payer: accounts.payer,
+        system_program: accounts.system_program,
+        rent: accounts.rent,
+    };
+    let create_metadata_cpi_ctx = CpiContext::new_with_signer(
+        accounts.token_metadata_program,
+        create_metadata_cpi_accounts,
+        signer,
+    );
+
+    create_metadata_accounts_v3(create_metadata_cpi_ctx, data_v2, true, true, None)?;
+    msg!("Metadata Account Created !!!");
+    Ok(())
+}
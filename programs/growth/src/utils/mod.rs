@@ -1,3 +1,15 @@
+mod ed25519;
+mod fees;
+mod merkle;
+mod metadata;
 mod realloc;
+mod rewards;
+mod team;
 
+pub use ed25519::*;
+pub use fees::*;
+pub use merkle::*;
+pub use metadata::*;
 pub use realloc::*;
+pub use rewards::*;
+pub use team::*;
@@ -1,4 +1,4 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, system_program};
 
 pub trait Realloc<'info> {
     fn realloc(
@@ -8,3 +8,36 @@ pub trait Realloc<'info> {
         system_program: &Program<'info, System>,
     ) -> Result<()>;
 }
+
+// For accounts that can't be loaded as their type until they've grown.
+impl<'info> Realloc<'info> for UncheckedAccount<'info> {
+    fn realloc(
+        &mut self,
+        space_to_add: usize,
+        payer: &Signer<'info>,
+        system_program: &Program<'info, System>,
+    ) -> Result<()> {
+        let account_info = self.to_account_info();
+        let new_account_size = account_info.data_len() + space_to_add;
+
+        // Determine additional rent required
+        let lamports_required = (Rent::get()?).minimum_balance(new_account_size);
+        let additional_rent_to_fund = lamports_required.saturating_sub(account_info.lamports());
+
+        // Perform transfer of additional rent
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account_info.clone(),
+                },
+            ),
+            additional_rent_to_fund,
+        )?;
+
+        // Reallocate the account
+        account_info.realloc(new_account_size, false)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, TokenAccount, Transfer};
+
+use crate::{
+    errors::GrowthError,
+    events::{LevelRewardPaid, ReviewRewardPaid},
+    state::{Org, ReviewerRewards, RewardClaims, Score},
+};
+
+pub struct RewardAccounts<'a, 'info> {
+    pub claims: &'a mut Account<'info, RewardClaims>,
+    pub claims_bump: u8,
+    pub vault: Option<&'a Account<'info, TokenAccount>>,
+    pub member_token_account: Option<&'a Account<'info, TokenAccount>>,
+    pub token_program: AccountInfo<'info>,
+}
+
+impl<'a, 'info> RewardAccounts<'a, 'info> {
+    pub fn from_optional(
+        claims: Option<&'a mut Account<'info, RewardClaims>>,
+        claims_bump: Option<u8>,
+        vault: Option<&'a Account<'info, TokenAccount>>,
+        member_token_account: Option<&'a Account<'info, TokenAccount>>,
+        token_program: AccountInfo<'info>,
+    ) -> Option<Self> {
+        Some(Self {
+            claims: claims?,
+            claims_bump: claims_bump?,
+            vault,
+            member_token_account,
+            token_program,
+        })
+    }
+}
+
+// Rewards are recorded as owed first and paid out of the vault when it can cover
+// them, a missing token account or an empty vault never holds back a level change.
+pub fn pay_level_rewards<'info>(
+    org: &Account<'info, Org>,
+    score: &Account<'info, Score>,
+    rewards: Option<RewardAccounts<'_, 'info>>,
+    old_levels: &[u8],
+) -> Result<()> {
+    if !org.pays_level_rewards() {
+        return Ok(());
+    }
+    let rewards = rewards.ok_or(GrowthError::RewardClaimsMissing)?;
+    let amount = rewards.claims.claim(
+        org,
+        score.key(),
+        old_levels,
+        &score.levels,
+        rewards.claims_bump,
+    );
+    rewards.claims.owed += amount;
+    pay_owed_rewards(
+        org,
+        score,
+        rewards.claims,
+        rewards.vault,
+        rewards.member_token_account,
+        rewards.token_program,
+    )
+}
+
+pub fn pay_owed_rewards<'info>(
+    org: &Account<'info, Org>,
+    score: &Account<'info, Score>,
+    claims: &mut Account<'info, RewardClaims>,
+    vault: Option<&Account<'info, TokenAccount>>,
+    member_token_account: Option<&Account<'info, TokenAccount>>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    let amount = claims.owed;
+    if amount == 0 {
+        return Ok(());
+    }
+    let (vault, member_token_account) = match (vault, member_token_account) {
+        (Some(vault), Some(member_token_account)) if vault.amount >= amount => {
+            (vault, member_token_account)
+        }
+        _ => {
+            msg!("Level reward owed:{}", amount);
+            return Ok(());
+        }
+    };
+    msg!("Paying level reward:{}", amount);
+    transfer_from_vault(org, vault, member_token_account, token_program, amount)?;
+    claims.owed = 0;
+    emit!(LevelRewardPaid {
+        org: org.key(),
+        member: score.applicant,
+        mint: org.reward_mint,
+        amount,
+        levels: score.levels.clone(),
+    });
+    Ok(())
+}
+
+fn transfer_from_vault<'info>(
+    org: &Account<'info, Org>,
+    vault: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let org_mint = org.mint.key();
+    let signer: &[&[&[u8]]] = &[&[
+        b"org",
+        org_mint.as_ref(),
+        org.authority.as_ref(),
+        &[org.bump],
+    ]];
+    transfer(
+        CpiContext::new_with_signer(
+            token_program,
+            Transfer {
+                from: vault.to_account_info(),
+                to: to.to_account_info(),
+                authority: org.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )
+}
+
+pub struct ReviewRewardAccounts<'a, 'info> {
+    pub reviewer_rewards: &'a mut Account<'info, ReviewerRewards>,
+    pub reviewer_rewards_bump: u8,
+    pub vault: &'a Account<'info, TokenAccount>,
+    pub reviewer_token_account: &'a Account<'info, TokenAccount>,
+    pub token_program: AccountInfo<'info>,
+}
+
+// Returns the amount credited for the review, which is what a revocation claws
+// back. Part of it may have only settled an earlier clawback instead of being paid.
+pub fn pay_review_reward<'info>(
+    org: &Account<'info, Org>,
+    reviewer: Pubkey,
+    rewards: ReviewRewardAccounts<'_, 'info>,
+) -> Result<u64> {
+    rewards
+        .reviewer_rewards
+        .ensure_initialized(org.key(), reviewer, rewards.reviewer_rewards_bump);
+    let (earned, amount) = rewards
+        .reviewer_rewards
+        .earn(org, Clock::get()?.unix_timestamp);
+    if amount == 0 {
+        return Ok(earned);
+    }
+    msg!("Paying review reward:{}", amount);
+    transfer_from_vault(
+        org,
+        rewards.vault,
+        rewards.reviewer_token_account,
+        rewards.token_program,
+        amount,
+    )?;
+    emit!(ReviewRewardPaid {
+        org: org.key(),
+        reviewer,
+        mint: org.reward_mint,
+        amount,
+    });
+    Ok(earned)
+}
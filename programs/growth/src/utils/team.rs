@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::GrowthError,
+    state::{Org, Score, Team},
+};
+
+// Moves the member's share of their team's aggregates from the scores and levels
+// they had before the instruction to the current ones.
+pub fn sync_team(
+    org: &Org,
+    score: &Account<Score>,
+    team: Option<&mut Account<Team>>,
+    old_scores: &[f32],
+    old_levels: &[u8],
+) -> Result<()> {
+    if score.team == Pubkey::default() {
+        return Ok(());
+    }
+    let team = team.ok_or(GrowthError::TeamAccountMissing)?;
+    assert_eq!(team.key(), score.team);
+    team.aggregate
+        .remove_contribution(org, old_scores, old_levels)?;
+    team.aggregate
+        .add_contribution(org, &score.scores, &score.levels)
+}
@@ -0,0 +1,62 @@
+// anchor's `Error` is what every instruction returns
+#![allow(clippy::result_large_err)]
+
+use anchor_lang::prelude::*;
+use growth::{
+    cpi::{accounts::LevelCheckCTX, assert_level, get_levels},
+    program::Growth,
+};
+
+declare_id!("ArSvab8fZ4PrxNrCzWyDTweVBmNKrThT8ZUtKBb75a4z");
+
+// Example consumer of the growth level checks: only members at or above
+// `min_level` in `range_group` of the org can call `gated_action`.
+#[program]
+pub mod level_gate {
+    use super::*;
+
+    pub fn gated_action(
+        ctx: Context<GatedActionCTX>,
+        range_group: u8,
+        min_level: u8,
+    ) -> Result<()> {
+        assert_level(
+            CpiContext::new(
+                ctx.accounts.growth_program.to_account_info(),
+                ctx.accounts.level_check(),
+            ),
+            range_group,
+            min_level,
+        )?;
+        let levels = get_levels(CpiContext::new(
+            ctx.accounts.growth_program.to_account_info(),
+            ctx.accounts.level_check(),
+        ))?
+        .get();
+        msg!("member:{} levels:{:?}", ctx.accounts.member.key(), levels);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct GatedActionCTX<'info> {
+    pub member: Signer<'info>,
+    /// CHECK: The growth program checks it's the member's score in the org
+    pub score: UncheckedAccount<'info>,
+    /// CHECK: Owned by the growth program, checked there
+    pub org: UncheckedAccount<'info>,
+    /// CHECK: The growth program checks it belongs to the score's mint
+    pub metadata: UncheckedAccount<'info>,
+    pub growth_program: Program<'info, Growth>,
+}
+
+impl<'info> GatedActionCTX<'info> {
+    fn level_check(&self) -> LevelCheckCTX<'info> {
+        LevelCheckCTX {
+            applicant: self.member.to_account_info(),
+            score: self.score.to_account_info(),
+            org: self.org.to_account_info(),
+            metadata: self.metadata.to_account_info(),
+        }
+    }
+}
@@ -3,7 +3,9 @@ import * as fs from "fs";
 import * as path from "path";
 import { Program } from "@coral-xyz/anchor";
 import { Growth } from "../target/types/growth";
+import { LevelGate } from "../target/types/level_gate";
 import {
+  Ed25519Program,
   Keypair,
   LAMPORTS_PER_SOL,
   PublicKey,
@@ -15,8 +17,10 @@ import {
   createMint,
   getAssociatedTokenAddressSync,
   getOrCreateAssociatedTokenAccount,
+  mintTo,
 } from "@solana/spl-token";
 
+import { keccak_256 } from "@noble/hashes/sha3";
 import {
   Metaplex,
   keypairIdentity,
@@ -39,6 +43,7 @@ describe("Growth", () => {
   const metaplex = Metaplex.make(env.connection);
 
   const program = anchor.workspace.Growth as Program<Growth>;
+  const levelGate = anchor.workspace.LevelGate as Program<LevelGate>;
 
   const getMetadata = (mint: PublicKey) => {
     return PublicKey.findProgramAddressSync(
@@ -115,6 +120,69 @@ describe("Growth", () => {
 
   const scoreAddress = getScore(orgAddress, applicant.publicKey);
 
+  const candidate = Keypair.generate();
+
+  let rewardVault: PublicKey;
+  let memberTokenAccount: PublicKey;
+
+  const getPromotion = (score: PublicKey) => {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("promotion"), score.toBuffer()],
+      program.programId
+    )[0];
+  };
+
+  const getOverrideRecord = (
+    orgAddress: PublicKey,
+    applicant: PublicKey,
+    sequence: number
+  ) => {
+    const seq = Buffer.alloc(4);
+    seq.writeUInt32LE(sequence);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("override"), orgAddress.toBuffer(), applicant.toBuffer(), seq],
+      program.programId
+    )[0];
+  };
+
+  const getReviewerCounter = (reviewer: PublicKey) => {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("reviewer_counter"), orgAddress.toBuffer(), reviewer.toBuffer()],
+      program.programId
+    )[0];
+  };
+
+  const getPairCounter = (reviewer: PublicKey, applicant: PublicKey) => {
+    return PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("pair_counter"),
+        orgAddress.toBuffer(),
+        reviewer.toBuffer(),
+        applicant.toBuffer(),
+      ],
+      program.programId
+    )[0];
+  };
+
+  const getReviewRecord = (score: PublicKey, index: number) => {
+    const indexBytes = Buffer.alloc(4);
+    indexBytes.writeUInt32LE(index);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("review"), score.toBuffer(), indexBytes],
+      program.programId
+    )[0];
+  };
+
+  const nextReviewRecord = async (score: PublicKey = scoreAddress) =>
+    getReviewRecord(score, (await program.account.score.fetch(score)).reviewCount);
+
+  const getHistory = (score: PublicKey) => {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("history"), score.toBuffer()],
+      program.programId
+    )[0];
+  };
+
   it("Is Creating Org!", async () => {
     let orgMintATA = getAssociatedTokenAddressSync(
       orgMint.publicKey,
@@ -165,6 +233,140 @@ describe("Growth", () => {
     const orgAccount = await program.account.org.fetch(orgAddress);
     console.log("org account data: ", orgAccount);
   });
+  it("Is setting scoring method", async () => {
+    const tx = await program.methods
+      .setScoringMethod({ trimmedMean: {} })
+      .accounts({
+        authority: authority.publicKey,
+        org: orgAddress,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Set scoring method signature", tx);
+    const orgAccount = await program.account.org.fetch(orgAddress);
+    console.log("org scoring method: ", orgAccount.scoringMethod);
+  });
+  it("Is setting confidence", async () => {
+    const tx = await program.methods
+      .setConfidence(1.96)
+      .accounts({
+        authority: authority.publicKey,
+        org: orgAddress,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Set confidence signature", tx);
+  });
+  it("Is setting review limits", async () => {
+    const tx = await program.methods
+      .setReviewLimits(
+        toBigNumber(86400),
+        100,
+        toBigNumber(86400),
+        50,
+        toBigNumber(0)
+      )
+      .accounts({
+        authority: authority.publicKey,
+        org: orgAddress,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Set review limits signature", tx);
+    const orgAccount = await program.account.org.fetch(orgAddress);
+    console.log("org max reviews per cycle: ", orgAccount.maxReviewsPerCycle);
+    if (
+      orgAccount.maxReviewsPerCycle !== 100 ||
+      orgAccount.maxPairReviewsPerPeriod !== 50 ||
+      orgAccount.reviewerCycle.toNumber() !== 86400
+    ) {
+      throw new Error("review limits weren't stored");
+    }
+
+    let rejected = false;
+    try {
+      await program.methods
+        .setReviewLimits(
+          new anchor.BN(-1),
+          100,
+          toBigNumber(86400),
+          50,
+          toBigNumber(0)
+        )
+        .accounts({
+          authority: authority.publicKey,
+          org: orgAddress,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+    } catch (e) {
+      rejected = true;
+    }
+    if (!rejected) {
+      throw new Error("negative reviewer cycle accepted");
+    }
+  });
+  it("Is setting min reviews sent", async () => {
+    const tx = await program.methods
+      .setMinReviewsSent(0, false)
+      .accounts({
+        authority: authority.publicKey,
+        org: orgAddress,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Set min reviews sent signature", tx);
+    const orgAccount = await program.account.org.fetch(orgAddress);
+    if (orgAccount.minReviewsSent !== 0 || orgAccount.reviewsSentPerPeriod) {
+      throw new Error("min reviews sent wasn't stored");
+    }
+  });
+  it("Is setting relationship weights", async () => {
+    // manager reviews count double, self assessments stay out of the level math
+    const tx = await program.methods
+      .setRelationshipWeights([2, 1, 1, 1], false)
+      .accounts({
+        authority: authority.publicKey,
+        org: orgAddress,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Set relationship weights signature", tx);
+  });
+  it("Is setting registration fee", async () => {
+    const tx = await program.methods
+      .setRegistrationFee(
+        toBigNumber(LAMPORTS_PER_SOL / 100),
+        PublicKey.default,
+        { authority: {} }
+      )
+      .accounts({
+        authority: authority.publicKey,
+        org: orgAddress,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Set registration fee signature", tx);
+  });
   it("Is Registering!", async () => {
     const applicanMint = await createMint(
       env.connection,
@@ -202,7 +404,7 @@ describe("Growth", () => {
         tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
       })
-      .signers([authority])
+      .signers([authority, applicant])
       .rpc({
         skipPreflight: true,
         commitment: "confirmed",
@@ -210,6 +412,184 @@ describe("Growth", () => {
     console.log("Register signature", tx1);
     let scoreAccount = await program.account.score.fetch(scoreAddress);
     console.log(`score account data: submited_score on ${scoreAccount.lastUpdate.toString()}`, scoreAccount);
+
+    // registering the same applicant again must not reset the score account
+    const secondMint = Keypair.generate();
+    await createMint(
+      env.connection,
+      applicant,
+      orgAddress,
+      orgAddress,
+      0,
+      secondMint,
+      {
+        commitment: "confirmed",
+      }
+    );
+    const secondMintATA = await getOrCreateAssociatedTokenAccount(
+      env.connection,
+      authority,
+      secondMint.publicKey,
+      applicant.publicKey
+    );
+    let rejected = false;
+    try {
+      await program.methods
+        .register("Saber", Buffer.from([1, 1]), toBigNumber(Date.now()/1000))
+        .accounts({
+          authority: authority.publicKey,
+          applicant: applicant.publicKey,
+          org: orgAddress,
+          collectionMaster: orgMaster,
+          score: scoreAddress,
+          registerMint: secondMint.publicKey,
+          metadata: getMetadata(secondMint.publicKey),
+          tokenAccount: secondMintATA.address,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([authority, applicant])
+        .rpc();
+    } catch (e) {
+      rejected = true;
+    }
+    if (!rejected) {
+      throw new Error("registered an applicant twice");
+    }
+  });
+  it("Is applying and approving application", async () => {
+    await env.connection.confirmTransaction(
+      await env.connection.requestAirdrop(candidate.publicKey, LAMPORTS_PER_SOL),
+      "confirmed"
+    );
+    let tx = await program.methods
+      .apply("Candidate")
+      .accounts({
+        applicant: candidate.publicKey,
+        org: orgAddress,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([candidate])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Apply signature", tx);
+
+    const candidateMint = Keypair.generate();
+    await createMint(
+      env.connection,
+      authority,
+      orgAddress,
+      orgAddress,
+      0,
+      candidateMint,
+      {
+        commitment: "confirmed",
+      }
+    );
+    const candidateATA = await getOrCreateAssociatedTokenAccount(
+      env.connection,
+      authority,
+      candidateMint.publicKey,
+      candidate.publicKey
+    );
+    tx = await program.methods
+      .approveApplication(Buffer.from([0, 0]), toBigNumber(Date.now() / 1000))
+      .accounts({
+        authority: authority.publicKey,
+        applicant: candidate.publicKey,
+        org: orgAddress,
+        registerMint: candidateMint.publicKey,
+        tokenAccount: candidateATA.address,
+        metadata: getMetadata(candidateMint.publicKey),
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([authority])
+      .rpc({
+        skipPreflight: true,
+        commitment: "confirmed",
+      });
+    console.log("Approve application signature", tx);
+    const scoreAccount = await program.account.score.fetch(
+      getScore(orgAddress, candidate.publicKey)
+    );
+    console.log("candidate score account: ", scoreAccount);
+  });
+  it("Is registering with allowlist proof", async () => {
+    const member = Keypair.generate();
+    await env.connection.confirmTransaction(
+      await env.connection.requestAirdrop(member.publicKey, LAMPORTS_PER_SOL),
+      "confirmed"
+    );
+    const startingLevels = Buffer.from([1, 0]);
+    // single leaf tree, the root is the leaf itself and the proof is empty
+    const root = keccak_256(
+      Buffer.concat([
+        Buffer.from([0]),
+        member.publicKey.toBuffer(),
+        startingLevels,
+      ])
+    );
+    let tx = await program.methods
+      .setAllowlistRoot(Array.from(root))
+      .accounts({
+        authority: authority.publicKey,
+        org: orgAddress,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Set allowlist root signature", tx);
+
+    const memberMint = Keypair.generate();
+    await createMint(
+      env.connection,
+      member,
+      orgAddress,
+      orgAddress,
+      0,
+      memberMint,
+      {
+        commitment: "confirmed",
+      }
+    );
+    const memberATA = await getOrCreateAssociatedTokenAccount(
+      env.connection,
+      member,
+      memberMint.publicKey,
+      member.publicKey
+    );
+    tx = await program.methods
+      .registerWithProof("Allowlisted", startingLevels, [])
+      .accounts({
+        applicant: member.publicKey,
+        org: orgAddress,
+        registerMint: memberMint.publicKey,
+        tokenAccount: memberATA.address,
+        metadata: getMetadata(memberMint.publicKey),
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([member])
+      .rpc({
+        skipPreflight: true,
+        commitment: "confirmed",
+      });
+    console.log("Register with proof signature", tx);
+    const scoreAccount = await program.account.score.fetch(
+      getScore(orgAddress, member.publicKey)
+    );
+    console.log("allowlisted score account: ", scoreAccount);
   });
   it("Is Verifying!", async () => {
     const txVerify = await program.methods
@@ -234,11 +614,12 @@ describe("Growth", () => {
     await wait(2);
     let score = [10, 10, 10, 10, 10, 10, 10, 10, 10, 10];
     let tx3 = await program.methods
-      .receiveScore(Buffer.from(score))
+      .receiveScore(score, toBigNumber(0), { peer: {} })
       .accounts({
         authority: authority.publicKey,
         applicant: applicant.publicKey,
         org: orgAddress,
+        reviewRecord: await nextReviewRecord(),
         metadata: registerMetadataAddress,
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -255,11 +636,12 @@ describe("Growth", () => {
     await wait(6);
     score = [40, 50, 50, 55, 55, 59, 50, 50, 55, 50];
     tx3 = await program.methods
-      .receiveScore(Buffer.from(score))
+      .receiveScore(score, toBigNumber(0), { peer: {} })
       .accounts({
         authority: authority.publicKey,
         applicant: applicant.publicKey,
         org: orgAddress,
+        reviewRecord: await nextReviewRecord(),
         metadata: registerMetadataAddress,
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -274,11 +656,12 @@ describe("Growth", () => {
     await wait(2);
     score = [100, 100, 50, 60, 45, 90, 50, 80, 90, 90];
     tx3 = await program.methods
-      .receiveScore(Buffer.from(score))
+      .receiveScore(score, toBigNumber(0), { peer: {} })
       .accounts({
         authority: authority.publicKey,
         applicant: applicant.publicKey,
         org: orgAddress,
+        reviewRecord: await nextReviewRecord(),
         metadata: registerMetadataAddress,
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -291,13 +674,14 @@ describe("Growth", () => {
     console.log(`score account data: submited_score[${score}] `, scoreAccount);
 
     await wait(10);
-    score = [100, 100, null, null, null, null, null, null, null, null];
+    score = [100, 100, 0, 0, 0, 0, 0, 0, 0, 0];
     tx3 = await program.methods
-      .receiveScore(Buffer.from(score))
+      .receiveScore(score, toBigNumber(0), { peer: {} })
       .accounts({
         authority: authority.publicKey,
         applicant: applicant.publicKey,
         org: orgAddress,
+        reviewRecord: await nextReviewRecord(),
         metadata: registerMetadataAddress,
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -309,11 +693,64 @@ describe("Growth", () => {
     scoreAccount = await program.account.score.fetch(scoreAddress);
     console.log(`score account data: submited_score[${score}] `, scoreAccount);
 
+    if (scoreAccount.scoresMin[0] !== 10 || scoreAccount.scoresMax[0] !== 100) {
+      throw new Error("min/max don't match the submitted extremes");
+    }
+    // trimmed mean drops the 10 and one 100 from the first two criteria
+    const plainMean =
+      ((scoreAccount.scoresSum[0] / scoreAccount.weightedReviews[0]) * 4 +
+        scoreAccount.scoresSum[1] / scoreAccount.weightedReviews[1]) /
+      5;
+    if (!(scoreAccount.scores[0] > plainMean)) {
+      throw new Error("trimmed score didn't drop the lowest review");
+    }
+    // (10² + 40² + 100² + 100²) / (10 + 40 + 100 + 100), whatever the review weight
+    const sqRatio = scoreAccount.scoresSqSum[0] / scoreAccount.scoresSum[0];
+    if (Math.abs(sqRatio - 86.8) > 0.01) {
+      throw new Error(`squared sum out of step with the reviews: ${sqRatio}`);
+    }
+
     let mplxMint = await metaplex.nfts().findByMint({
       mintAddress: registerMint.publicKey,
     });
     console.log("MPLX", JSON.stringify(mplxMint));
   });
+  it("Is rejecting foreign metadata accounts", async () => {
+    const receiveWith = async (metadata: PublicKey, tokenMetadataProgram: PublicKey) =>
+      program.methods
+        .receiveScore(
+          [50, 50, 50, 50, 50, 50, 50, 50, 50, 50],
+          toBigNumber(0),
+          { peer: {} }
+        )
+        .accounts({
+          authority: authority.publicKey,
+          applicant: applicant.publicKey,
+          org: orgAddress,
+          reviewRecord: await nextReviewRecord(),
+          metadata,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          tokenMetadataProgram,
+        })
+        .signers([authority])
+        .rpc();
+
+    for (const [metadata, tokenMetadataProgram, what] of [
+      [orgMetadataAddress, TOKEN_METADATA_PROGRAM_ID, "another mint's metadata"],
+      [registerMetadataAddress, SystemProgram.programId, "a wrong metadata program"],
+    ] as [PublicKey, PublicKey, string][]) {
+      let rejected = false;
+      try {
+        await receiveWith(metadata, tokenMetadataProgram);
+      } catch (e) {
+        rejected = true;
+      }
+      if (!rejected) {
+        throw new Error(`review accepted with ${what}`);
+      }
+    }
+  });
   it("Is bulk load scores", async () => {
     let tx3 = await program.methods
       .updateScores(
@@ -321,12 +758,14 @@ describe("Growth", () => {
         Buffer.from([2, 2, 2, 2, 2, 2, 2, 2, 2, 2]),
         toBigNumber("1692393205"),
         Buffer.from([2, 2]),
-        true
+        true,
+        Array.from(Buffer.alloc(32, 1))
       )
       .accounts({
         authority: authority.publicKey,
         applicant: applicant.publicKey,
         org: orgAddress,
+        overrideRecord: getOverrideRecord(orgAddress, applicant.publicKey, 0),
         metadata: registerMetadataAddress,
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -339,8 +778,62 @@ describe("Growth", () => {
     console.log("Scoring signature", tx3);
     let scoreAccount = await program.account.score.fetch(scoreAddress);
     console.log(`score account data: `, scoreAccount);
+    const overrideRecord = await program.account.overrideRecord.fetch(
+      getOverrideRecord(orgAddress, applicant.publicKey, 0)
+    );
+    console.log("override record: ", overrideRecord);
+  });
+  it("Is revoking reviews after an override", async () => {
+    // the overwrite left two reviews per criterion, three recorded reviews are revoked
+    for (const index of [0, 1, 2]) {
+      const tx = await program.methods
+        .revokeReview(index)
+        .accounts({
+          signer: authority.publicKey,
+          applicant: applicant.publicKey,
+          org: orgAddress,
+          reviewRecord: getReviewRecord(scoreAddress, index),
+        })
+        .signers([authority])
+        .rpc({
+          commitment: "confirmed",
+        });
+      console.log("Revoke overwritten review signature", tx);
+    }
+    const scoreAccount = await program.account.score.fetch(scoreAddress);
+    console.log(`score account data: `, scoreAccount);
+    if (scoreAccount.reviewsRecieved[0] !== 0 || scoreAccount.scoresSum[0] !== 0) {
+      throw new Error("criterion not cleared after revoking past the overwrite");
+    }
+
+    // load the same scores again so the following tests start from the overwrite
+    const tx = await program.methods
+      .updateScores(
+        Buffer.from([100, 80, 100, 100, 180, 170, 160, 150, 130, 150]),
+        Buffer.from([2, 2, 2, 2, 2, 2, 2, 2, 2, 2]),
+        toBigNumber("1692393205"),
+        Buffer.from([2, 2]),
+        true,
+        Array.from(Buffer.alloc(32, 1))
+      )
+      .accounts({
+        authority: authority.publicKey,
+        applicant: applicant.publicKey,
+        org: orgAddress,
+        overrideRecord: getOverrideRecord(orgAddress, applicant.publicKey, 1),
+        metadata: registerMetadataAddress,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Reload scores signature", tx);
   });
   it("Is sending scores", async () => {
+    const before = await program.account.score.fetch(scoreAddress);
     let tx3 = await program.methods
       .sendScore()
       .accounts({
@@ -359,5 +852,988 @@ describe("Growth", () => {
     console.log("Scoring signature", tx3);
     let scoreAccount = await program.account.score.fetch(scoreAddress);
     console.log(`score account data: `, scoreAccount);
+    if (scoreAccount.reviewsSent !== before.reviewsSent + 1) {
+      throw new Error("send_score didn't count the review sent");
+    }
+  });
+  it("Is receiving signed scores", async () => {
+    // borsh encoded SignedReview { org, applicant, scores, nonce, relationship }
+    const scores = [60, 60, 60, 60, 60, 60, 60, 60, 60, 60];
+    const message = Buffer.alloc(32 + 32 + 4 + 4 * scores.length + 8 + 1);
+    orgAddress.toBuffer().copy(message, 0);
+    applicant.publicKey.toBuffer().copy(message, 32);
+    message.writeUInt32LE(scores.length, 64);
+    scores.forEach((s, i) => message.writeFloatLE(s, 68 + 4 * i));
+    message.writeBigUInt64LE(BigInt(1), 68 + 4 * scores.length);
+    message.writeUInt8(1, 76 + 4 * scores.length); // Relationship::Peer
+
+    const tx = await program.methods
+      .receiveSignedScore(scores, toBigNumber(1), { peer: {} })
+      .accounts({
+        relayer: env.wallet.publicKey,
+        reviewer: candidate.publicKey,
+        reviewerScore: getScore(orgAddress, candidate.publicKey),
+        applicant: applicant.publicKey,
+        org: orgAddress,
+        reviewRecord: await nextReviewRecord(),
+        metadata: registerMetadataAddress,
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .preInstructions([
+        Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: candidate.secretKey,
+          message,
+        }),
+      ])
+      .rpc({
+        skipPreflight: true,
+      });
+    console.log("Signed scoring signature", tx);
+    const reviewerScore = await program.account.score.fetch(
+      getScore(orgAddress, candidate.publicKey)
+    );
+    console.log("reviewer nonce: ", reviewerScore.reviewNonce.toString());
+  });
+  it("Is disputing and revoking reviews", async () => {
+    let scoreAccount = await program.account.score.fetch(scoreAddress);
+    const index = scoreAccount.reviewCount;
+    const indexBytes = Buffer.alloc(4);
+    indexBytes.writeUInt32LE(index);
+    const reviewRecord = PublicKey.findProgramAddressSync(
+      [Buffer.from("review"), scoreAddress.toBuffer(), indexBytes],
+      program.programId
+    )[0];
+    let tx = await program.methods
+      .receiveScore(
+        [10, 10, 10, 10, 10, 10, 10, 10, 10, 10],
+        toBigNumber(0),
+        { peer: {} }
+      )
+      .accounts({
+        authority: authority.publicKey,
+        applicant: applicant.publicKey,
+        org: orgAddress,
+        reviewRecord,
+        metadata: registerMetadataAddress,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([authority])
+      .rpc({
+        skipPreflight: true,
+        commitment: "confirmed",
+      });
+    console.log("Recorded review signature", tx);
+
+    await env.connection.confirmTransaction(
+      await env.connection.requestAirdrop(applicant.publicKey, LAMPORTS_PER_SOL),
+      "confirmed"
+    );
+    const dispute = PublicKey.findProgramAddressSync(
+      [Buffer.from("dispute"), reviewRecord.toBuffer()],
+      program.programId
+    )[0];
+    tx = await program.methods
+      .openDispute(index, Array.from(Buffer.alloc(32, 2)))
+      .accounts({
+        applicant: applicant.publicKey,
+        org: orgAddress,
+        reviewRecord,
+        dispute,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([applicant])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Open dispute signature", tx);
+
+    tx = await program.methods
+      .resolveDispute(index, true, Array.from(Buffer.alloc(32, 3)))
+      .accounts({
+        authority: authority.publicKey,
+        applicant: applicant.publicKey,
+        org: orgAddress,
+        reviewRecord,
+        dispute,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Resolve dispute signature", tx);
+    const record = await program.account.reviewRecord.fetch(reviewRecord);
+    console.log("revoked review: ", record);
+  });
+  it("Is importing backdated reviews", async () => {
+    const importReview = async () => {
+      const scoreAccount = await program.account.score.fetch(scoreAddress);
+      return program.methods
+        .receiveScore(
+          [7, 7, 7, 7, 7, 7, 7, 7, 7, 7],
+          new anchor.BN(Math.floor(Date.now() / 1000) - 60),
+          { peer: {} }
+        )
+        .accounts({
+          authority: authority.publicKey,
+          applicant: applicant.publicKey,
+          org: orgAddress,
+          reviewRecord: getReviewRecord(scoreAddress, scoreAccount.reviewCount),
+          metadata: registerMetadataAddress,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        })
+        .signers([authority])
+        .rpc({
+          commitment: "confirmed",
+        });
+    };
+
+    let rejected = false;
+    try {
+      await importReview();
+    } catch (e) {
+      rejected = true;
+    }
+    if (!rejected) {
+      throw new Error("backdated review accepted with imports disabled");
+    }
+
+    rejected = false;
+    try {
+      await program.methods
+        .setMaxBackdate(toBigNumber(0), true)
+        .accounts({
+          authority: authority.publicKey,
+          org: orgAddress,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+    } catch (e) {
+      rejected = true;
+    }
+    if (!rejected) {
+      throw new Error("imports enabled with a zero backdate window");
+    }
+
+    let tx = await program.methods
+      .setMaxBackdate(toBigNumber(3600), true)
+      .accounts({
+        authority: authority.publicKey,
+        org: orgAddress,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Enable import signature", tx);
+
+    tx = await importReview();
+    console.log("Import review signature", tx);
+
+    tx = await program.methods
+      .setMaxBackdate(toBigNumber(0), false)
+      .accounts({
+        authority: authority.publicKey,
+        org: orgAddress,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Disable import signature", tx);
+  });
+  it("Is pausing", async () => {
+    const setPaused = (paused: number) =>
+      program.methods
+        .setPaused(paused)
+        .accounts({
+          authority: authority.publicKey,
+          org: orgAddress,
+        })
+        .signers([authority])
+        .rpc({
+          commitment: "confirmed",
+        });
+    const review = async () => {
+      const scoreAccount = await program.account.score.fetch(scoreAddress);
+      return program.methods
+        .receiveScore(
+          [8, 8, 8, 8, 8, 8, 8, 8, 8, 8],
+          toBigNumber(0),
+          { peer: {} }
+        )
+        .accounts({
+          authority: authority.publicKey,
+          applicant: applicant.publicKey,
+          org: orgAddress,
+          reviewRecord: getReviewRecord(scoreAddress, scoreAccount.reviewCount),
+          metadata: registerMetadataAddress,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        })
+        .signers([authority])
+        .rpc({
+          commitment: "confirmed",
+        });
+    };
+
+    // reviews paused
+    let tx = await setPaused(2);
+    console.log("Pause reviews signature", tx);
+    let rejected = false;
+    try {
+      await review();
+    } catch (e) {
+      rejected = true;
+    }
+    if (!rejected) {
+      throw new Error("review accepted while reviews are paused");
+    }
+
+    // metadata paused, the review still lands and only the uri waits
+    tx = await setPaused(8);
+    console.log("Pause metadata signature", tx);
+    tx = await review();
+    console.log("Review with metadata paused signature", tx);
+
+    tx = await setPaused(0);
+    console.log("Resume signature", tx);
+    tx = await program.methods
+      .syncMetadata()
+      .accounts({
+        cranker: authority.publicKey,
+        applicant: applicant.publicKey,
+        org: orgAddress,
+        metadata: registerMetadataAddress,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Sync metadata signature", tx);
+  });
+  it("Is reassigning managers", async () => {
+    const tx = await program.methods
+      .reassignManager(candidate.publicKey)
+      .accounts({
+        authority: authority.publicKey,
+        applicant: applicant.publicKey,
+        org: orgAddress,
+        history: getHistory(scoreAddress),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Reassign manager signature", tx);
+    const scoreAccount = await program.account.score.fetch(scoreAddress);
+    console.log("manager: ", scoreAccount.manager.toBase58());
+    const history = await program.account.scoreHistory.fetch(
+      getHistory(scoreAddress)
+    );
+    console.log("history: ", history.entries);
+  });
+  it("Is weighting reviews by reviewer level", async () => {
+    let tx = await program.methods
+      .setReputationWeights([1, 2, 3])
+      .accounts({
+        authority: authority.publicKey,
+        org: orgAddress,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Set reputation weights signature", tx);
+
+    const counterBefore = await program.account.reviewCounter.fetchNullable(
+      getReviewerCounter(candidate.publicKey)
+    );
+    const reviewerBefore = await program.account.score.fetch(
+      getScore(orgAddress, candidate.publicKey)
+    );
+    tx = await program.methods
+      .receiveScore(
+        [80, 80, 80, 80, 80, 80, 80, 80, 80, 80],
+        toBigNumber(0),
+        { peer: {} }
+      )
+      .accounts({
+        authority: authority.publicKey,
+        applicant: applicant.publicKey,
+        org: orgAddress,
+        reviewerScore: getScore(orgAddress, candidate.publicKey),
+        // limits are counted against the member the review is submitted for
+        reviewerCounter: getReviewerCounter(candidate.publicKey),
+        pairCounter: getPairCounter(candidate.publicKey, applicant.publicKey),
+        reviewRecord: await nextReviewRecord(),
+        metadata: registerMetadataAddress,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([authority])
+      .rpc({
+        skipPreflight: true,
+        commitment: "confirmed",
+      });
+    console.log("Weighted scoring signature", tx);
+    const scoreAccount = await program.account.score.fetch(scoreAddress);
+    console.log(`score account data: `, scoreAccount);
+    const counterAfter = await program.account.reviewCounter.fetch(
+      getReviewerCounter(candidate.publicKey)
+    );
+    if (counterAfter.count !== (counterBefore?.count ?? 0) + 1) {
+      throw new Error("review not counted against the reviewer it's submitted for");
+    }
+    const reviewerAfter = await program.account.score.fetch(
+      getScore(orgAddress, candidate.publicKey)
+    );
+    if (reviewerAfter.reviewsSent !== reviewerBefore.reviewsSent + 1) {
+      throw new Error("review not added to the reviewer's reviews sent");
+    }
+  });
+  it("Is evaluating level", async () => {
+    let tx = await program.methods
+      .evaluateLevel()
+      .accounts({
+        cranker: env.wallet.publicKey,
+        applicant: applicant.publicKey,
+        org: orgAddress,
+        metadata: registerMetadataAddress,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .rpc({
+        skipPreflight: true,
+      });
+    console.log("Evaluate level signature", tx);
+    let scoreAccount = await program.account.score.fetch(scoreAddress);
+    console.log(`score account data: `, scoreAccount);
+  });
+  it("Is previewing level", async () => {
+    const preview = await program.methods
+      .previewLevel()
+      .accounts({
+        applicant: applicant.publicKey,
+        org: orgAddress,
+        metadata: registerMetadataAddress,
+      })
+      .view();
+    console.log("level preview: ", preview);
+    // the imported 7s sit far from the bulk loaded averages
+    if (!(preview.variances[0] > 0) || !(preview.standardErrors[0] > 0)) {
+      throw new Error("no spread computed from the squared sums");
+    }
+  });
+  it("Is reading score history", async () => {
+    const history = await program.account.scoreHistory.fetch(
+      getHistory(scoreAddress)
+    );
+    console.log("score history: ", JSON.stringify(history.entries));
+  });
+  it("Is routing promotions through approval", async () => {
+    let tx = await program.methods
+      .setPromotionApproval(true, authority.publicKey)
+      .accounts({
+        authority: authority.publicKey,
+        org: orgAddress,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Set promotion approval signature", tx);
+
+    await wait(6);
+    tx = await program.methods
+      .receiveScore(
+        [100, 100, 100, 100, 100, 100, 100, 100, 100, 100],
+        toBigNumber(0),
+        { peer: {} }
+      )
+      .accounts({
+        authority: authority.publicKey,
+        applicant: applicant.publicKey,
+        org: orgAddress,
+        promotion: getPromotion(scoreAddress),
+        reviewRecord: await nextReviewRecord(),
+        metadata: registerMetadataAddress,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([authority])
+      .rpc({
+        skipPreflight: true,
+      });
+    console.log("Scoring signature", tx);
+    const promotion = await program.account.pendingPromotion.fetch(
+      getPromotion(scoreAddress)
+    );
+    console.log("pending promotion: ", promotion);
+
+    tx = await program.methods
+      .approvePromotion()
+      .accounts({
+        approver: authority.publicKey,
+        applicant: applicant.publicKey,
+        org: orgAddress,
+        metadata: registerMetadataAddress,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([authority])
+      .rpc({
+        skipPreflight: true,
+      });
+    console.log("Approve promotion signature", tx);
+    let scoreAccount = await program.account.score.fetch(scoreAddress);
+    console.log(`score account data: `, scoreAccount);
+  });
+  it("Is paying level rewards", async () => {
+    const rewardMint = await createMint(
+      env.connection,
+      authority,
+      authority.publicKey,
+      null,
+      0
+    );
+    rewardVault = (
+      await getOrCreateAssociatedTokenAccount(
+        env.connection,
+        authority,
+        rewardMint,
+        orgAddress,
+        true
+      )
+    ).address;
+    await mintTo(
+      env.connection,
+      authority,
+      rewardMint,
+      rewardVault,
+      authority,
+      1000
+    );
+    memberTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        env.connection,
+        authority,
+        rewardMint,
+        applicant.publicKey
+      )
+    ).address;
+    const rewardClaims = PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_claims"), scoreAddress.toBuffer()],
+      program.programId
+    )[0];
+    let tx = await program.methods
+      .setLevelRewards(rewardMint, [
+        [toBigNumber(10), toBigNumber(20), toBigNumber(30)],
+        [toBigNumber(10), toBigNumber(20)],
+      ])
+      .accounts({
+        authority: authority.publicKey,
+        org: orgAddress,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Set level rewards signature", tx);
+
+    tx = await program.methods
+      .evaluateLevel()
+      .accounts({
+        cranker: env.wallet.publicKey,
+        applicant: applicant.publicKey,
+        org: orgAddress,
+        rewardClaims,
+        rewardVault,
+        memberTokenAccount,
+        metadata: registerMetadataAddress,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc({
+        skipPreflight: true,
+        commitment: "confirmed",
+      });
+    console.log("Evaluate level signature", tx);
+
+    // whatever the vault couldn't cover stays owed until someone claims it
+    const claims = await program.account.rewardClaims.fetch(rewardClaims);
+    console.log("reward claims: ", claims);
+    if (claims.owed.toNumber() > 0) {
+      tx = await program.methods
+        .claimLevelRewards()
+        .accounts({
+          cranker: env.wallet.publicKey,
+          applicant: applicant.publicKey,
+          org: orgAddress,
+          rewardClaims,
+          rewardVault,
+          memberTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc({
+          commitment: "confirmed",
+        });
+      console.log("Claim level rewards signature", tx);
+    }
+  });
+  it("Is paying review rewards", async () => {
+    let tx = await program.methods
+      .setReviewReward(toBigNumber(5), toBigNumber(50))
+      .accounts({
+        authority: authority.publicKey,
+        org: orgAddress,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Set review reward signature", tx);
+
+    // candidate reviews applicant, then gets rewarded for that review
+    const reviewerRewards = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("reviewer_rewards"),
+        orgAddress.toBuffer(),
+        candidate.publicKey.toBuffer(),
+      ],
+      program.programId
+    )[0];
+    const candidateTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        env.connection,
+        authority,
+        (await program.account.org.fetch(orgAddress)).rewardMint,
+        candidate.publicKey
+      )
+    ).address;
+    const index = (await program.account.score.fetch(scoreAddress)).reviewCount;
+    const reviewRecord = getReviewRecord(scoreAddress, index);
+    tx = await program.methods
+      .receiveScore(
+        [50, 50, 50, 50, 50, 50, 50, 50, 50, 50],
+        toBigNumber(0),
+        { peer: {} }
+      )
+      .accounts({
+        authority: authority.publicKey,
+        applicant: applicant.publicKey,
+        org: orgAddress,
+        reviewerScore: getScore(orgAddress, candidate.publicKey),
+        reviewerCounter: getReviewerCounter(candidate.publicKey),
+        pairCounter: getPairCounter(candidate.publicKey, applicant.publicKey),
+        reviewRecord,
+        rewardClaims: PublicKey.findProgramAddressSync(
+          [Buffer.from("reward_claims"), scoreAddress.toBuffer()],
+          program.programId
+        )[0],
+        rewardVault,
+        memberTokenAccount,
+        metadata: registerMetadataAddress,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Review by candidate signature", tx);
+
+    tx = await program.methods
+      .claimReviewReward(index)
+      .accounts({
+        authority: authority.publicKey,
+        applicant: candidate.publicKey,
+        reviewee: applicant.publicKey,
+        revieweeScore: scoreAddress,
+        org: orgAddress,
+        reviewRecord,
+        reviewerRewards,
+        rewardVault,
+        memberTokenAccount: candidateTokenAccount,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Claim review reward signature", tx);
+    console.log(
+      "rewarded review: ",
+      await program.account.reviewRecord.fetch(reviewRecord)
+    );
+
+    // revoking the review claws its reward back
+    tx = await program.methods
+      .revokeReview(index)
+      .accounts({
+        signer: authority.publicKey,
+        applicant: applicant.publicKey,
+        org: orgAddress,
+        reviewRecord,
+        reviewerRewards,
+        reviewerScore: getScore(orgAddress, candidate.publicKey),
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Revoke rewarded review signature", tx);
+    const rewards = await program.account.reviewerRewards.fetch(
+      reviewerRewards
+    );
+    console.log("reviewer rewards: ", rewards);
+  });
+  it("Is collecting reviews through commit-reveal", async () => {
+    const setPeriod = async (period: number) =>
+      program.methods
+        .setReviewLimits(
+          toBigNumber(86400),
+          100,
+          toBigNumber(period),
+          50,
+          toBigNumber(0)
+        )
+        .accounts({
+          authority: authority.publicKey,
+          org: orgAddress,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc({
+          commitment: "confirmed",
+        });
+    const setCommitReveal = async (enabled: boolean, window: number) =>
+      program.methods
+        .setCommitReveal(enabled, toBigNumber(window))
+        .accounts({
+          authority: authority.publicKey,
+          org: orgAddress,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc({
+          commitment: "confirmed",
+        });
+    // 20 second cycles, the last 10 seconds are for reveals
+    await setPeriod(20);
+    await setCommitReveal(true, 10);
+    const secondsIntoCycle = () => Math.floor(Date.now() / 1000) % 20;
+    while (secondsIntoCycle() < 1 || secondsIntoCycle() > 6) {
+      await wait(1);
+    }
+    const cycle = Math.floor(Date.now() / 1000 / 20);
+
+    const scores = [70, 70, 70, 70, 70, 70, 70, 70, 70, 70];
+    const salt = Buffer.alloc(32, 7);
+    const scoreBytes = Buffer.alloc(4 * scores.length);
+    scores.forEach((s, i) => scoreBytes.writeFloatLE(s, 4 * i));
+    const commitment = Array.from(
+      keccak_256(
+        Buffer.concat([
+          applicant.publicKey.toBuffer(),
+          scoreBytes,
+          Buffer.from([1]), // peer
+          salt,
+        ])
+      )
+    );
+    let tx = await program.methods
+      .commitReview(commitment)
+      .accounts({
+        reviewer: candidate.publicKey,
+        org: orgAddress,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([candidate])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Commit review signature", tx);
+
+    while (secondsIntoCycle() < 12) {
+      await wait(1);
+    }
+    tx = await program.methods
+      .revealReview(commitment, scores, { peer: {} }, Array.from(salt))
+      .accounts({
+        reviewer: candidate.publicKey,
+        reviewerScore: getScore(orgAddress, candidate.publicKey),
+        applicant: applicant.publicKey,
+        org: orgAddress,
+        reviewRecord: await nextReviewRecord(),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([candidate])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Reveal review signature", tx);
+
+    await wait(10);
+    tx = await program.methods
+      .settleReveals(toBigNumber(cycle))
+      .accounts({
+        cranker: env.wallet.publicKey,
+        applicant: applicant.publicKey,
+        org: orgAddress,
+      })
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Settle reveals signature", tx);
+    const scoreAccount = await program.account.score.fetch(scoreAddress);
+    console.log(`score account data: `, scoreAccount);
+
+    await setCommitReveal(false, 0);
+    await setPeriod(86400);
+  });
+  it("Is grouping members into teams", async () => {
+    const name = "core";
+    const team = PublicKey.findProgramAddressSync(
+      [Buffer.from("team"), orgAddress.toBuffer(), Buffer.from(name)],
+      program.programId
+    )[0];
+    let tx = await program.methods
+      .createTeam(name, candidate.publicKey)
+      .accounts({
+        authority: authority.publicKey,
+        org: orgAddress,
+        team,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Create team signature", tx);
+
+    tx = await program.methods
+      .addTeamMember()
+      .accounts({
+        authority: authority.publicKey,
+        applicant: applicant.publicKey,
+        org: orgAddress,
+        team,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Add team member signature", tx);
+    let teamAccount = await program.account.team.fetch(team);
+    console.log("team: ", teamAccount);
+
+    tx = await program.methods
+      .removeTeamMember()
+      .accounts({
+        authority: authority.publicKey,
+        applicant: applicant.publicKey,
+        org: orgAddress,
+        team,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Remove team member signature", tx);
+    teamAccount = await program.account.team.fetch(team);
+    console.log("team members: ", teamAccount.aggregate.memberCount);
+  });
+  it("Is rolling scores up into a parent org", async () => {
+    const parentMint = Keypair.generate();
+    const parentAddress = getOrg(parentMint.publicKey, authority.publicKey);
+    let tx = await program.methods
+      .createOrganization(
+        Buffer.from([1, 1, 1, 1, 1, 1, 1, 1, 1, 1]),
+        Buffer.from([]),
+        [[30, 60, 90]],
+        Buffer.from("Designity Group"),
+        2,
+        "https://public.designity.software",
+        5
+      )
+      .accounts({
+        org: parentAddress,
+        orgMint: parentMint.publicKey,
+        authority: authority.publicKey,
+        metadata: getMetadata(parentMint.publicKey),
+        masterEdition: getMasterEdition(parentMint.publicKey),
+        tokenAccount: getAssociatedTokenAddressSync(
+          parentMint.publicKey,
+          parentAddress,
+          true
+        ),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([authority, parentMint])
+      .rpc({
+        commitment: "confirmed",
+        skipPreflight: true,
+      });
+    console.log("Create parent org signature", tx);
+
+    // both of the org's range groups report into the parent's only one
+    tx = await program.methods
+      .setParentOrg(Buffer.from([0, 0]))
+      .accounts({
+        authority: authority.publicKey,
+        parentAuthority: authority.publicKey,
+        org: orgAddress,
+        parent: parentAddress,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Set parent org signature", tx);
+
+    const rollup = PublicKey.findProgramAddressSync(
+      [Buffer.from("rollup"), parentAddress.toBuffer(), orgAddress.toBuffer()],
+      program.programId
+    )[0];
+    const rolledUpScore = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("rollup_score"),
+        parentAddress.toBuffer(),
+        scoreAddress.toBuffer(),
+      ],
+      program.programId
+    )[0];
+    tx = await program.methods
+      .rollUpScore()
+      .accounts({
+        cranker: env.wallet.publicKey,
+        applicant: applicant.publicKey,
+        org: orgAddress,
+        parent: parentAddress,
+        rollup,
+        rolledUpScore,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Roll up score signature", tx);
+    console.log(
+      "rolled up score: ",
+      await program.account.rolledUpScore.fetch(rolledUpScore)
+    );
+    console.log("org rollup: ", await program.account.orgRollup.fetch(rollup));
+
+    // setting the map again starts the rollup over
+    tx = await program.methods
+      .setParentOrg(Buffer.from([0, 0]))
+      .accounts({
+        authority: authority.publicKey,
+        parentAuthority: authority.publicKey,
+        org: orgAddress,
+        parent: parentAddress,
+        rollup,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Reset parent org signature", tx);
+    const resetRollup = await program.account.orgRollup.fetch(rollup);
+    if (resetRollup.aggregate.memberCount != 0) {
+      throw new Error("rollup kept members from the previous epoch");
+    }
+  });
+  it("Is gating on levels through CPI", async () => {
+    const levels = await program.methods
+      .getLevels()
+      .accounts({
+        applicant: applicant.publicKey,
+        org: orgAddress,
+        metadata: registerMetadataAddress,
+      })
+      .view();
+    console.log("levels: ", levels);
+
+    const tx = await levelGate.methods
+      .gatedAction(0, levels[0])
+      .accounts({
+        member: applicant.publicKey,
+        score: scoreAddress,
+        org: orgAddress,
+        metadata: registerMetadataAddress,
+        growthProgram: program.programId,
+      })
+      .signers([applicant])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Gated action signature", tx);
+
+    let rejected = false;
+    try {
+      await levelGate.methods
+        .gatedAction(0, levels[0] + 1)
+        .accounts({
+          member: applicant.publicKey,
+          score: scoreAddress,
+          org: orgAddress,
+          metadata: registerMetadataAddress,
+          growthProgram: program.programId,
+        })
+        .signers([applicant])
+        .rpc();
+    } catch (e) {
+      rejected = (e.logs ?? []).some((log: string) =>
+        log.includes("LevelTooLow")
+      );
+    }
+    if (!rejected) {
+      throw new Error("gated action not rejected with LevelTooLow");
+    }
+  });
+  it("Is withdrawing treasury", async () => {
+    const treasury = PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), orgAddress.toBuffer()],
+      program.programId
+    )[0];
+    const tx = await program.methods
+      .withdrawTreasury(toBigNumber(LAMPORTS_PER_SOL / 100))
+      .accounts({
+        authority: authority.publicKey,
+        org: orgAddress,
+        treasury,
+        destination: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([authority])
+      .rpc({
+        skipPreflight: true,
+      });
+    console.log("Withdraw treasury signature", tx);
   });
 });